@@ -0,0 +1,211 @@
+mod report;
+mod stream;
+
+use std::{cmp::Reverse, error::Error};
+
+use common::{
+    Puzzle,
+    explain::Explain,
+    generate::{Generate, InputRng},
+    parse::{Cursor, ParseContext},
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+pub use report::Report;
+pub use stream::stream;
+
+/// The snacks one elf carries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elf {
+    /// The calories of all the snacks together.
+    pub calories: u32,
+    /// How many snacks there are.
+    pub items: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// How many of the elves carrying the most calories part 2 adds up.
+    pub top: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { top: 3 }
+    }
+}
+
+pub struct Day01;
+
+impl Puzzle for Day01 {
+    const DAY: &'static str = "01";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    /// The elves in the order they are listed.
+    type Input = Vec<Elf>;
+    type Output1 = u32;
+    type Output2 = u32;
+    type Config = Config;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(context.blocks(input, 0, |elf_calories| {
+            let mut cursor = Cursor::new(elf_calories);
+            let mut elf = Elf { calories: cursor.number::<u32>()?, items: 1 };
+            while !cursor.is_empty() {
+                cursor.tag("\n")?;
+                let calories = cursor.number::<u32>()?;
+                elf.calories = elf.calories.checked_add(calories).ok_or_else(|| cursor.expected("fewer calories in total"))?;
+                elf.items += 1;
+            }
+            Ok(elf)
+        })?)
+    }
+
+    fn part1(elves: &Self::Input, _config: &Self::Config) -> Result<u32, Box<dyn Error>> {
+        most_calories(&ranking(elves))
+    }
+
+    fn part2(elves: &Self::Input, config: &Self::Config) -> Result<u32, Box<dyn Error>> {
+        top_calories(&ranking(elves), config)
+    }
+}
+
+/// Each elf's number, counting from 1, with what it carries, most calories
+/// first. Elves carrying the same amount keep their order.
+pub fn ranking(elves: &[Elf]) -> Vec<(usize, Elf)> {
+    let mut ranking = (1..).zip(elves.iter().copied()).collect::<Vec<_>>();
+    ranking.sort_by_key(|&(_, elf)| Reverse(elf.calories));
+    ranking
+}
+
+/// The first elves of a ranking that part 2 adds up, or all of them if there are fewer.
+fn top<'r>(ranking: &'r [(usize, Elf)], config: &Config) -> &'r [(usize, Elf)] {
+    &ranking[..config.top.min(ranking.len())]
+}
+
+/// The calories carried by the first elf of a ranking, the answer to part 1.
+pub fn most_calories(ranking: &[(usize, Elf)]) -> Result<u32, Box<dyn Error>> {
+    let (_, elf) = ranking.first().ok_or("no elves")?;
+    Ok(elf.calories)
+}
+
+/// The calories carried by the top N elves of a ranking together, the answer to part 2.
+pub fn top_calories(ranking: &[(usize, Elf)], config: &Config) -> Result<u32, Box<dyn Error>> {
+    top(ranking, config)
+        .iter()
+        .try_fold(0u32, |sum, (_, elf)| sum.checked_add(elf.calories))
+        .ok_or_else(|| format!("the top {} elves carry too many calories", config.top).into())
+}
+
+impl Explain for Day01 {
+    /// The calories of every elf, largest first, and which elves make up the top N.
+    fn explain(elves: &Self::Input, config: &Self::Config) -> Result<Vec<String>, Box<dyn Error>> {
+        let ranking = ranking(elves);
+        let mut lines = ranking
+            .iter()
+            .map(|(number, elf)| format!("elf {number} carries {} calories", elf.calories))
+            .collect::<Vec<_>>();
+        let top = top(&ranking, config);
+        let numbers = top.iter().map(|(number, _)| number.to_string()).collect::<Vec<_>>();
+        let calories = top.iter().map(|(_, elf)| elf.calories.to_string()).collect::<Vec<_>>();
+        lines.push(format!(
+            "the top {} are elves {} carrying {} = {} calories",
+            config.top,
+            numbers.join(", "),
+            calories.join(" + "),
+            Self::part2(elves, config)?
+        ));
+        Ok(lines)
+    }
+}
+
+impl Generate for Day01 {
+    /// One block of snacks per elf, with at least three elves for part 2.
+    fn generate(rng: &mut InputRng, size: usize, _config: &Self::Config) -> String {
+        (0..size.max(3))
+            .map(|_| {
+                let snacks = rng.gen_range(1..=15);
+                (0..snacks).map(|_| rng.gen_range(1_000..=70_000).to_string()).collect::<Vec<_>>().join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{
+        Answers, Puzzle,
+        explain::Explain,
+        generate::{Generate, input_rng},
+    };
+
+    use super::{Config, Day01, Elf, ranking};
+
+    const EXAMPLE: &str = "\
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day01::solve(EXAMPLE)?;
+        assert_eq!(answers, Answers { part1: 24000, part2: 45000 });
+        Ok(())
+    }
+
+    #[test]
+    fn top_n() -> Result<(), Box<dyn Error>> {
+        assert_eq!(Day01::solve_with(EXAMPLE, &Config { top: 2 })?.part2, 35000);
+        assert_eq!(Day01::solve_with(EXAMPLE, &Config { top: 10 })?.part2, 55000);
+        Ok(())
+    }
+
+    #[test]
+    fn equal_totals() -> Result<(), Box<dyn Error>> {
+        let input = "3000\n\n1000\n2000\n\n3000\n\n2000";
+        let answers = Day01::solve(input)?;
+        assert_eq!(answers, Answers { part1: 3000, part2: 9000 });
+        let elves = [3000, 3000, 1000, 3000].map(|calories| Elf { calories, items: 1 });
+        let numbers = ranking(&elves).into_iter().map(|(number, _)| number).collect::<Vec<_>>();
+        assert_eq!(numbers, [1, 2, 4, 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day01::explain_str(EXAMPLE, &Default::default())?;
+        assert_eq!(explanation[0], "elf 4 carries 24000 calories");
+        assert_eq!(explanation.last().unwrap(), "the top 3 are elves 4, 3, 5 carrying 24000 + 11000 + 10000 = 45000 calories");
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day01::solve(&read_to_string(Day01::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: 73211, part2: 213958 });
+        Ok(())
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let input = Day01::generate(&mut input_rng(1), 500, &Default::default());
+        Day01::solve(&input)?;
+        Ok(())
+    }
+}
@@ -1,23 +1,76 @@
-use std::{collections::BTreeSet, error::Error, fs::read_to_string};
+use std::{error::Error, time::Instant};
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let elf_calories_string = read_to_string("input.txt")?;
+use clap::{Parser, Subcommand};
+use common::{
+    Answers, Puzzle, Timings,
+    cli::{DayArgs, InputArgs, print_skipped},
+    config::ConfigArgs,
+    output::{Format, print_answers},
+    parse::ParseContext,
+};
+use day01::{Day01, Report, most_calories, stream, top_calories};
 
-    let total_elf_calories = elf_calories_string
-        .split("\n\n")
-        .map(|elf_calories| {
-            elf_calories
-                .split('\n')
-                .filter_map(|n| n.parse::<u32>().ok())
-                .sum::<u32>()
-        })
-        .collect::<BTreeSet<_>>();
+/// Solves day 1 of Advent of Code 2022, or reports on the calories the elves carry.
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    day: DayArgs,
+}
 
-    let elf_with_max_calories = total_elf_calories.iter().last().ok_or("no elves")?;
-    println!("2022-12-01 Part 1: {elf_with_max_calories}");
+#[derive(Subcommand)]
+enum Command {
+    /// Prints how many elves there are, statistics about their calories, the top N and their snacks
+    Report {
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        config: ConfigArgs,
+        /// How to print the report
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Solves the puzzle reading one line at a time, keeping only the top N elves in memory
+    Stream {
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        config: ConfigArgs,
+        /// How to print the answers
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+}
 
-    let top_three_elves_with_max_calories = total_elf_calories.iter().rev().take(3).sum::<u32>();
-    Ok(println!(
-        "2022-12-01 Part 2: {top_three_elves_with_max_calories}"
-    ))
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    match cli.command {
+        None => common::cli::run::<Day01>(cli.day),
+        Some(Command::Report { input, config, format }) => {
+            let config = config.load(Day01::DAY)?;
+            let text = input.read(Day01::INPUT_PATH)?;
+            let mut context = ParseContext::new(Day01::DAY, input.mode());
+            let elves = Day01::parse(&text, &mut context);
+            print_skipped(&context);
+            Report::new(&elves?, &config)?.print(format);
+            Ok(())
+        }
+        Some(Command::Stream { input, config, format }) => {
+            let config = config.load(Day01::DAY)?;
+            let reader = input.open(Day01::INPUT_PATH)?;
+            let mut context = ParseContext::new(Day01::DAY, input.mode());
+            let start = Instant::now();
+            let top = stream(reader, &mut context, &config);
+            let parse = start.elapsed();
+            print_skipped(&context);
+            // Both parts are read off the same few elves, so they take no time of their own.
+            let top = top?;
+            let answers = Answers { part1: most_calories(&top)?, part2: top_calories(&top, &config)? };
+            let timings = Timings { parse, part1: Default::default(), part2: Default::default() };
+            print_answers(Day01::DAY, &answers.to_strings(), &timings, format);
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,138 @@
+use std::error::Error;
+
+use common::output::Format;
+use serde::Serialize;
+
+use crate::{Config, Elf, ranking, top};
+
+/// The percentages of elves the report gives the calories for.
+const PERCENTILES: [u8; 5] = [10, 25, 75, 90, 99];
+
+/// Statistics about the elves and the calories they carry.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Report {
+    pub elves: usize,
+    pub total: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub percentiles: Vec<Percentile>,
+    /// The elves carrying the most calories, most first.
+    pub top: Vec<RankedElf>,
+    /// What the elves in `top` carry together.
+    pub top_total: u64,
+    /// How many snacks each elf carries, in the order the elves are listed.
+    pub items: Vec<usize>,
+}
+
+/// The most calories that `percent` of the elves carry, by the nearest rank.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Percentile {
+    pub percent: u8,
+    pub calories: u32,
+}
+
+/// An elf, counting from 1, with what it carries.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct RankedElf {
+    pub elf: usize,
+    pub calories: u32,
+    pub items: usize,
+}
+
+impl Report {
+    pub fn new(elves: &[Elf], config: &Config) -> Result<Self, Box<dyn Error>> {
+        if elves.is_empty() {
+            return Err("no elves".into());
+        }
+        let mut calories = elves.iter().map(|elf| elf.calories).collect::<Vec<_>>();
+        calories.sort_unstable();
+        let count = calories.len();
+        let total = calories.iter().map(|&calories| u64::from(calories)).sum::<u64>();
+        let median = match count % 2 {
+            0 => (f64::from(calories[count / 2 - 1]) + f64::from(calories[count / 2])) / 2.0,
+            _ => f64::from(calories[count / 2]),
+        };
+        let percentiles = PERCENTILES
+            .iter()
+            .map(|&percent| {
+                let rank = (usize::from(percent) * count).div_ceil(100).max(1);
+                Percentile { percent, calories: calories[rank - 1] }
+            })
+            .collect();
+        let top = top(&ranking(elves), config)
+            .iter()
+            .map(|&(elf, Elf { calories, items })| RankedElf { elf, calories, items })
+            .collect::<Vec<_>>();
+        Ok(Self {
+            elves: count,
+            total,
+            mean: total as f64 / count as f64,
+            median,
+            percentiles,
+            top_total: top.iter().map(|elf| u64::from(elf.calories)).sum(),
+            top,
+            items: elves.iter().map(|elf| elf.items).collect(),
+        })
+    }
+
+    /// The report as lines meant for people.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("{} elves carry {} calories", self.elves, self.total),
+            format!("mean: {:.1} calories", self.mean),
+            format!("median: {:.1} calories", self.median),
+        ];
+        lines.extend(
+            self.percentiles
+                .iter()
+                .map(|percentile| format!("{}th percentile: {} calories", percentile.percent, percentile.calories)),
+        );
+        lines.push(format!("the top {} carry {} calories:", self.top.len(), self.top_total));
+        lines.extend(
+            self.top
+                .iter()
+                .map(|elf| format!("  elf {} with {} calories in {} items", elf.elf, elf.calories, elf.items)),
+        );
+        lines.push("items per elf:".to_owned());
+        lines.extend(self.items.iter().enumerate().map(|(index, items)| format!("  elf {}: {items}", index + 1)));
+        lines
+    }
+
+    /// Prints the report in the given format.
+    pub fn print(&self, format: Format) {
+        match format {
+            Format::Text => {
+                println!("2022-12-01 Report:");
+                for line in self.lines() {
+                    println!("  {line}");
+                }
+            }
+            Format::Json => println!("{}", serde_json::to_string(self).unwrap_or_default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use common::{Puzzle, parse::{Mode, ParseContext}};
+
+    use super::{Percentile, RankedElf, Report};
+    use crate::{Config, Day01};
+
+    #[test]
+    fn statistics() -> Result<(), Box<dyn Error>> {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+        let elves = Day01::parse(input, &mut ParseContext::new(Day01::DAY, Mode::Strict))?;
+        let report = Report::new(&elves, &Config { top: 2 })?;
+        assert_eq!((report.elves, report.total, report.mean, report.median), (5, 55000, 11000.0, 10000.0));
+        assert_eq!(report.percentiles[0], Percentile { percent: 10, calories: 4000 });
+        assert_eq!(report.percentiles[2], Percentile { percent: 75, calories: 11000 });
+        assert_eq!(report.top[1], RankedElf { elf: 3, calories: 11000, items: 2 });
+        assert_eq!(report.top_total, 35000);
+        assert_eq!(report.items, [3, 1, 2, 3, 1]);
+        assert_eq!(report.lines()[8], "the top 2 carry 35000 calories:");
+        Ok(())
+    }
+}
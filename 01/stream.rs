@@ -0,0 +1,148 @@
+use std::{cmp::Reverse, collections::BinaryHeap, error::Error, io::BufRead};
+
+use common::parse::{Cursor, Expected, ParseContext};
+
+use crate::{Config, Elf};
+
+/// The elves carrying the most calories among those seen so far. The heap
+/// holds the least of them on top, ready to make way for a better one;
+/// among equal amounts the later elf goes first, as in the ranking.
+struct TopElves {
+    size: usize,
+    heap: BinaryHeap<Reverse<(u32, Reverse<usize>, usize)>>,
+}
+
+impl TopElves {
+    fn new(size: usize) -> Self {
+        Self { size, heap: BinaryHeap::with_capacity(size + 1) }
+    }
+
+    fn push(&mut self, number: usize, elf: Elf) {
+        self.heap.push(Reverse((elf.calories, Reverse(number), elf.items)));
+        if self.heap.len() > self.size {
+            self.heap.pop();
+        }
+    }
+
+    /// The elves kept, ranked like `ranking` ranks all of them.
+    fn into_ranking(self) -> Vec<(usize, Elf)> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((calories, Reverse(number), items))| (number, Elf { calories, items }))
+            .collect()
+    }
+}
+
+/// Reads the elves one line at a time, keeping only the top N of them, or
+/// the first one if N is 0. Returns them ranked, each with its number.
+pub fn stream(mut reader: impl BufRead, context: &mut ParseContext, config: &Config) -> Result<Vec<(usize, Elf)>, Box<dyn Error>> {
+    let mut top = TopElves::new(config.top.max(1));
+    let mut elves = 0;
+    // The elf being read, or where and why it is malformed.
+    let mut elf: Option<Result<Elf, (usize, Expected)>> = None;
+    // A blank line where an elf should start, a mistake unless only blank lines follow.
+    let mut blank = None;
+    let mut line = String::new();
+    for index in 0.. {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let mut cursor = Cursor::new(line.trim_end_matches(['\n', '\r']));
+        if cursor.is_empty() {
+            match elf.take() {
+                Some(result) => {
+                    if let Some(elf) = finish(context, result)? {
+                        elves += 1;
+                        top.push(elves, elf);
+                    }
+                }
+                None => blank = blank.or(Some((index, cursor.expected("a number")))),
+            }
+            continue;
+        }
+        if let Some(blank) = blank.take() {
+            finish(context, Err(blank))?;
+        }
+        let calories = cursor.number::<u32>().and_then(|calories| cursor.end().map(|_| calories));
+        elf = Some(match (elf, calories) {
+            (Some(Err(error)), _) => Err(error),
+            (_, Err(expected)) => Err((index, expected)),
+            (None, Ok(calories)) => Ok(Elf { calories, items: 1 }),
+            (Some(Ok(elf)), Ok(calories)) => match elf.calories.checked_add(calories) {
+                Some(total) => Ok(Elf { calories: total, items: elf.items + 1 }),
+                None => Err((index, cursor.expected("fewer calories in total"))),
+            },
+        });
+    }
+    if let Some(result) = elf
+        && let Some(elf) = finish(context, result)?
+    {
+        elves += 1;
+        top.push(elves, elf);
+    }
+    Ok(top.into_ranking())
+}
+
+/// The elf just read, or `None` if it was malformed and lenient mode skips it.
+fn finish(context: &mut ParseContext, result: Result<Elf, (usize, Expected)>) -> Result<Option<Elf>, Box<dyn Error>> {
+    match result {
+        Ok(elf) => Ok(Some(elf)),
+        Err((line, expected)) => Ok(context.check(line, Err::<Elf, _>(expected))?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        error::Error,
+        fs::{File, read_to_string},
+        io::BufReader,
+    };
+
+    use common::{
+        Puzzle,
+        parse::{Mode, ParseContext},
+    };
+
+    use super::stream;
+    use crate::{Config, Day01, ranking};
+
+    fn context(mode: Mode) -> ParseContext {
+        ParseContext::new(Day01::DAY, mode)
+    }
+
+    #[test]
+    fn same_ranking_as_parsing() -> Result<(), Box<dyn Error>> {
+        let input = read_to_string(Day01::INPUT_PATH)?;
+        let elves = Day01::parse(input.trim_end(), &mut context(Mode::Strict))?;
+        let config = Config { top: 5 };
+        let streamed = stream(BufReader::new(File::open(Day01::INPUT_PATH)?), &mut context(Mode::Strict), &config)?;
+        assert_eq!(streamed, ranking(&elves)[..5]);
+        Ok(())
+    }
+
+    #[test]
+    fn equal_totals_and_line_endings() -> Result<(), Box<dyn Error>> {
+        let input = "3000\r\n\r\n1000\r\n2000\r\n\r\n3000\n\n2000\n\n\n";
+        let numbers = stream(input.as_bytes(), &mut context(Mode::Strict), &Config { top: 3 })?
+            .into_iter()
+            .map(|(number, elf)| (number, elf.calories, elf.items))
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, [(1, 3000, 1), (2, 3000, 2), (3, 3000, 1)]);
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_elves() {
+        let input = "1000\n\n2000\nx\n3000\n\n\n\n4000";
+        let error = stream(input.as_bytes(), &mut context(Mode::Strict), &Config::default()).unwrap_err();
+        assert_eq!(error.to_string(), "day 01, line 4, column 1: expected a number");
+        let mut context = context(Mode::Lenient);
+        let elves = stream(input.as_bytes(), &mut context, &Config::default()).unwrap();
+        assert_eq!(elves.iter().map(|(number, elf)| (*number, elf.calories)).collect::<Vec<_>>(), [(2, 4000), (1, 1000)]);
+        let skipped = context.skipped().iter().map(|error| error.line).collect::<Vec<_>>();
+        assert_eq!(skipped, [4, 7]);
+    }
+}
@@ -0,0 +1,224 @@
+use std::error::Error;
+
+use common::{
+    Puzzle,
+    config::NoConfig,
+    explain::Explain,
+    generate::{Generate, InputRng},
+    parse::{Cursor, Expected, ParseContext},
+};
+use rand::Rng;
+
+#[derive(Clone, Copy)]
+pub enum Choice {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Choice {
+    fn parse(cursor: &mut Cursor) -> Result<Self, Expected> {
+        match cursor.one_of(&["A", "B", "C", "X", "Y", "Z"])? {
+            "A" | "X" => Ok(Self::Rock),
+            "B" | "Y" => Ok(Self::Paper),
+            _ => Ok(Self::Scissors),
+        }
+    }
+
+    fn value(&self) -> usize {
+        match self {
+            Choice::Rock => 1,
+            Choice::Paper => 2,
+            Choice::Scissors => 3,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Choice::Rock => "rock",
+            Choice::Paper => "paper",
+            Choice::Scissors => "scissors",
+        }
+    }
+
+    fn lose(&self) -> Self {
+        match self {
+            Choice::Rock => Choice::Scissors,
+            Choice::Paper => Choice::Rock,
+            Choice::Scissors => Choice::Paper,
+        }
+    }
+
+    fn win(&self) -> Self {
+        match self {
+            Choice::Rock => Choice::Paper,
+            Choice::Paper => Choice::Scissors,
+            Choice::Scissors => Choice::Rock,
+        }
+    }
+}
+
+pub struct ChoicePair(Choice, Choice);
+
+impl ChoicePair {
+    fn parse(text: &str) -> Result<ChoicePair, Expected> {
+        let mut cursor = Cursor::new(text);
+        let fst = Choice::parse(&mut cursor)?;
+        cursor.tag(" ")?;
+        let snd = Choice::parse(&mut cursor)?;
+        cursor.end()?;
+        Ok(ChoicePair(fst, snd))
+    }
+
+    fn outcome(&self) -> usize {
+        match self {
+            ChoicePair(Choice::Rock, Choice::Paper)
+            | ChoicePair(Choice::Paper, Choice::Scissors)
+            | ChoicePair(Choice::Scissors, Choice::Rock) => 6,
+            ChoicePair(Choice::Rock, Choice::Rock)
+            | ChoicePair(Choice::Paper, Choice::Paper)
+            | ChoicePair(Choice::Scissors, Choice::Scissors) => 3,
+            ChoicePair(Choice::Rock, Choice::Scissors)
+            | ChoicePair(Choice::Paper, Choice::Rock)
+            | ChoicePair(Choice::Scissors, Choice::Paper) => 0,
+        }
+    }
+
+    fn adjust(&self) -> Self {
+        ChoicePair(
+            self.0,
+            match self.1 {
+                Choice::Rock => self.0.lose(),
+                Choice::Paper => self.0,
+                Choice::Scissors => self.0.win(),
+            },
+        )
+    }
+}
+
+pub struct Day02;
+
+impl Puzzle for Day02 {
+    const DAY: &'static str = "02";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = Vec<ChoicePair>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Config = NoConfig;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(context.lines(input, 0, ChoicePair::parse)?)
+    }
+
+    fn part1(choices: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        Ok(choices
+            .iter()
+            .map(|choices| choices.1.value() + choices.outcome())
+            .sum::<usize>())
+    }
+
+    fn part2(choices: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        Ok(choices
+            .iter()
+            .map(|choices| {
+                let adjusted_choice = choices.adjust();
+                adjusted_choice.1.value() + adjusted_choice.outcome()
+            })
+            .sum::<usize>())
+    }
+}
+
+impl Explain for Day02 {
+    /// The score of every round, read as our choice in part 1 and as the
+    /// outcome we need in part 2.
+    fn explain(choices: &Self::Input, _config: &Self::Config) -> Result<Vec<String>, Box<dyn Error>> {
+        Ok(choices
+            .iter()
+            .enumerate()
+            .map(|(index, choices)| {
+                let adjusted_choice = choices.adjust();
+                let goal = match choices.1 {
+                    Choice::Rock => "losing to",
+                    Choice::Paper => "drawing with",
+                    Choice::Scissors => "beating",
+                };
+                format!(
+                    "round {}: {} against {} scores {} + {}; {goal} {} needs {} and scores {} + {}",
+                    index + 1,
+                    choices.1.name(),
+                    choices.0.name(),
+                    choices.1.value(),
+                    choices.outcome(),
+                    choices.0.name(),
+                    adjusted_choice.1.name(),
+                    adjusted_choice.1.value(),
+                    adjusted_choice.outcome()
+                )
+            })
+            .collect())
+    }
+}
+
+impl Generate for Day02 {
+    /// One round per line.
+    fn generate(rng: &mut InputRng, size: usize, _config: &Self::Config) -> String {
+        (0..size)
+            .map(|_| format!("{} {}", rng.gen_range('A'..='C'), rng.gen_range('X'..='Z')))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{
+        Answers, Puzzle,
+        explain::Explain,
+        generate::{Generate, input_rng},
+    };
+
+    use super::Day02;
+
+    const EXAMPLE: &str = "\
+A Y
+B X
+C Z";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day02::solve(EXAMPLE)?;
+        assert_eq!(answers, Answers { part1: 15, part2: 12 });
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day02::explain_str(EXAMPLE, &Default::default())?;
+        assert_eq!(
+            explanation,
+            [
+                "round 1: paper against rock scores 2 + 6; drawing with rock needs rock and scores 1 + 3",
+                "round 2: rock against paper scores 1 + 0; losing to paper needs rock and scores 1 + 0",
+                "round 3: scissors against scissors scores 3 + 3; beating scissors needs rock and scores 1 + 6",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day02::solve(&read_to_string(Day02::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: 9241, part2: 14610 });
+        Ok(())
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let input = Day02::generate(&mut input_rng(1), 500, &Default::default());
+        Day02::solve(&input)?;
+        Ok(())
+    }
+}
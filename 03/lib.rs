@@ -0,0 +1,209 @@
+use std::{collections::HashSet, error::Error};
+
+use common::{
+    Puzzle,
+    config::NoConfig,
+    explain::Explain,
+    generate::{Generate, InputRng},
+    parse::{Cursor, Expected, ParseContext},
+};
+use rand::{Rng, seq::SliceRandom};
+
+pub struct Rucksack {
+    comp1: HashSet<char>,
+    comp2: HashSet<char>,
+}
+
+impl Rucksack {
+    fn parse(input_line: &str) -> Result<Self, Expected> {
+        let mut cursor = Cursor::new(input_line);
+        let items = cursor.take_while(|c| c.is_ascii_alphabetic());
+        if !cursor.is_empty() {
+            return Err(cursor.expected("an item letter"));
+        } else if !items.len().is_multiple_of(2) {
+            return Err(cursor.expected("an even number of items"));
+        }
+        let (comp1_chars, comp2_chars) = items.split_at(items.len() / 2);
+        Ok(Self {
+            comp1: comp1_chars.chars().collect(),
+            comp2: comp2_chars.chars().collect(),
+        })
+    }
+
+    fn union(&self) -> HashSet<char> {
+        self.comp1.union(&self.comp2).copied().collect()
+    }
+
+    /// The items in both compartments.
+    fn shared(&self) -> impl Iterator<Item = char> + '_ {
+        self.comp1.intersection(&self.comp2).copied()
+    }
+}
+
+/// The items every rucksack in a group holds.
+fn badges(group: &[Rucksack]) -> HashSet<char> {
+    group
+        .iter()
+        .fold(None, |total_option: Option<HashSet<_>>, rucksack| {
+            if let Some(total) = total_option {
+                Some(total.intersection(&rucksack.union()).copied().collect())
+            } else {
+                Some(rucksack.union())
+            }
+        })
+        .unwrap_or_default()
+}
+
+fn priority(c: char) -> usize {
+    if c.is_ascii_lowercase() {
+        c as usize - 'a' as usize + 1
+    } else {
+        c as usize - 'A' as usize + 27
+    }
+}
+
+/// The items in a set, in order, each with its priority.
+fn describe_items(items: impl IntoIterator<Item = char>) -> String {
+    let mut items = items.into_iter().collect::<Vec<_>>();
+    items.sort_unstable();
+    match items.is_empty() {
+        true => "nothing".to_string(),
+        false => items.iter().map(|item| format!("{item} ({})", priority(*item))).collect::<Vec<_>>().join(", "),
+    }
+}
+
+pub struct Day03;
+
+impl Puzzle for Day03 {
+    const DAY: &'static str = "03";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = Vec<Rucksack>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Config = NoConfig;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(context.lines(input, 0, Rucksack::parse)?)
+    }
+
+    fn part1(rucksacks: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        let priority_sum = rucksacks
+            .iter()
+            .map(|rucksack| rucksack.shared().map(priority).sum::<usize>())
+            .sum::<usize>();
+        Ok(priority_sum)
+    }
+
+    fn part2(rucksacks: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        let badge_priority_sums = rucksacks
+            .chunks_exact(3)
+            .map(|group| badges(group).into_iter().map(priority).sum::<usize>())
+            .sum::<usize>();
+        Ok(badge_priority_sums)
+    }
+}
+
+impl Explain for Day03 {
+    /// The item in both compartments of every rucksack, then the badge of
+    /// every group of three.
+    fn explain(rucksacks: &Self::Input, _config: &Self::Config) -> Result<Vec<String>, Box<dyn Error>> {
+        let shared = rucksacks.iter().enumerate().map(|(index, rucksack)| {
+            format!("rucksack {}: both compartments hold {}", index + 1, describe_items(rucksack.shared()))
+        });
+        let badges = rucksacks.chunks_exact(3).enumerate().map(|(index, group)| {
+            format!("group {}: the badge is {}", index + 1, describe_items(badges(group)))
+        });
+        Ok(shared.chain(badges).collect())
+    }
+}
+
+impl Generate for Day03 {
+    /// Rucksacks in groups of three, so `size` is rounded up to a multiple of three.
+    fn generate(rng: &mut InputRng, size: usize, _config: &Self::Config) -> String {
+        let items = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+        (0..size.div_ceil(3).max(1))
+            .flat_map(|_| {
+                let mut items = items.clone();
+                items.shuffle(rng);
+                let badge = items.pop().unwrap_or('a');
+                // Every elf of the group packs from its own third of the other
+                // items, so the badge is the only item all three carry.
+                items
+                    .chunks(items.len() / 3)
+                    .map(|pool| {
+                        let (shared, only) = pool.split_first().unwrap_or((&badge, &[]));
+                        let (left_only, right_only) = only.split_at(only.len() / 2);
+                        let length = rng.gen_range(2..=16);
+                        let badge_on_left = rng.gen_bool(0.5);
+                        let mut pack = |first: &[char], only: &[char]| {
+                            let mut compartment = first.to_vec();
+                            while compartment.len() < length {
+                                compartment.extend(only.choose(rng));
+                            }
+                            compartment.shuffle(rng);
+                            compartment
+                        };
+                        let (left, right) = if badge_on_left {
+                            (pack(&[*shared, badge], left_only), pack(&[*shared], right_only))
+                        } else {
+                            (pack(&[*shared], left_only), pack(&[*shared, badge], right_only))
+                        };
+                        left.into_iter().chain(right).collect::<String>()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{
+        Answers, Puzzle,
+        explain::Explain,
+        generate::{Generate, input_rng},
+    };
+
+    use super::Day03;
+
+    const EXAMPLE: &str = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day03::solve(EXAMPLE)?;
+        assert_eq!(answers, Answers { part1: 157, part2: 70 });
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day03::explain_str(EXAMPLE, &Default::default())?;
+        assert_eq!(explanation[0], "rucksack 1: both compartments hold p (16)");
+        assert_eq!(explanation[6..], ["group 1: the badge is r (18)", "group 2: the badge is Z (52)"]);
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day03::solve(&read_to_string(Day03::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: 8185, part2: 2817 });
+        Ok(())
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let input = Day03::generate(&mut input_rng(1), 500, &Default::default());
+        Day03::solve(&input)?;
+        Ok(())
+    }
+}
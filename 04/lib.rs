@@ -0,0 +1,181 @@
+use std::{error::Error, ops::RangeInclusive};
+
+use common::{
+    Puzzle,
+    config::NoConfig,
+    explain::Explain,
+    generate::{Generate, InputRng},
+    parse::{Cursor, Expected, ParseContext},
+};
+use rand::Rng;
+
+pub struct ElfRanges(RangeInclusive<usize>, RangeInclusive<usize>);
+
+impl ElfRanges {
+    fn parse(input: &str) -> Result<Self, Expected> {
+        let mut cursor = Cursor::new(input);
+        let range = |cursor: &mut Cursor| {
+            let start = cursor.number::<usize>()?;
+            cursor.tag("-")?;
+            let end = cursor.number::<usize>()?;
+            Ok::<_, Expected>(start..=end)
+        };
+        let first = range(&mut cursor)?;
+        cursor.tag(",")?;
+        let second = range(&mut cursor)?;
+        cursor.end()?;
+        Ok(Self(first, second))
+    }
+
+    fn is_total_overlap(&self) -> bool {
+        // Compared by their bounds, so that huge ranges cost no more than small ones.
+        let is_subset = |inner: &RangeInclusive<usize>, outer: &RangeInclusive<usize>| {
+            inner.is_empty() || (outer.start() <= inner.start() && inner.end() <= outer.end())
+        };
+        is_subset(&self.0, &self.1) || is_subset(&self.1, &self.0)
+    }
+
+    fn is_partial_overlap(&self) -> bool {
+        !self.0.is_empty() && !self.1.is_empty() && self.0.start() <= self.1.end() && self.1.start() <= self.0.end()
+    }
+}
+
+pub struct Day04;
+
+impl Puzzle for Day04 {
+    const DAY: &'static str = "04";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = Vec<ElfRanges>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Config = NoConfig;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(context.lines(input, 0, ElfRanges::parse)?)
+    }
+
+    fn part1(elf_ranges: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        let total_overlaps = elf_ranges
+            .iter()
+            .filter(|range| range.is_total_overlap())
+            .count();
+        Ok(total_overlaps)
+    }
+
+    fn part2(elf_ranges: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        let partial_overlaps = elf_ranges
+            .iter()
+            .filter(|range| range.is_partial_overlap())
+            .count();
+        Ok(partial_overlaps)
+    }
+}
+
+impl Explain for Day04 {
+    /// How the two ranges of every pair lie to each other.
+    fn explain(elf_ranges: &Self::Input, _config: &Self::Config) -> Result<Vec<String>, Box<dyn Error>> {
+        Ok(elf_ranges
+            .iter()
+            .enumerate()
+            .map(|(index, ranges)| {
+                let relation = if ranges.is_total_overlap() {
+                    "one contains the other"
+                } else if ranges.is_partial_overlap() {
+                    "they overlap"
+                } else {
+                    "they are apart"
+                };
+                format!(
+                    "pair {}: {}-{} and {}-{}, {relation}",
+                    index + 1,
+                    ranges.0.start(),
+                    ranges.0.end(),
+                    ranges.1.start(),
+                    ranges.1.end()
+                )
+            })
+            .collect())
+    }
+}
+
+impl Generate for Day04 {
+    /// One pair of section ranges per line.
+    fn generate(rng: &mut InputRng, size: usize, _config: &Self::Config) -> String {
+        let mut range = || {
+            let start = rng.gen_range(1..=99);
+            format!("{start}-{}", rng.gen_range(start..=99))
+        };
+        (0..size)
+            .map(|_| format!("{},{}", range(), range()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{
+        Answers, Puzzle,
+        explain::Explain,
+        generate::{Generate, input_rng},
+    };
+
+    use super::Day04;
+
+    const EXAMPLE: &str = "\
+2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day04::solve(EXAMPLE)?;
+        assert_eq!(answers, Answers { part1: 2, part2: 4 });
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day04::explain_str(EXAMPLE, &Default::default())?;
+        assert_eq!(explanation[..4], [
+            "pair 1: 2-4 and 6-8, they are apart",
+            "pair 2: 2-3 and 4-5, they are apart",
+            "pair 3: 5-7 and 7-9, they overlap",
+            "pair 4: 2-8 and 3-7, one contains the other",
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day04::solve(&read_to_string(Day04::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: 528, part2: 881 });
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_line() {
+        let error = Day04::solve("2-4,6-8\n2-3;4-5").unwrap_err();
+        assert_eq!(error.to_string(), "day 04, line 2, column 4: expected \",\"");
+    }
+
+    #[test]
+    fn huge_ranges() -> Result<(), Box<dyn Error>> {
+        let answers = Day04::solve("1-18446744073709551615,2-3\n5-4,1-9")?;
+        assert_eq!(answers, Answers { part1: 2, part2: 1 });
+        Ok(())
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let input = Day04::generate(&mut input_rng(1), 500, &Default::default());
+        Day04::solve(&input)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,402 @@
+use std::{collections::BTreeMap, error::Error};
+use common::{
+    Puzzle,
+    animate::{Animate, FrameSink},
+    config::NoConfig,
+    explain::Explain,
+    generate::{Generate, InputRng},
+    grid::Grid,
+    parse::{Cursor, Expected, ParseContext},
+    visualize::{Frame, Pixel, Rgb},
+};
+use rand::{Rng, seq::SliceRandom};
+
+type Stacks = BTreeMap<usize, Vec<char>>;
+
+fn top_crates(stacks: &Stacks) -> String {
+    stacks.values().filter_map(|stack| stack.last()).collect()
+}
+
+/// Moves the crates one at a time, like the CrateMover 9000.
+fn move_one_by_one(stacks: &mut Stacks, (count, source, target): &(usize, usize, usize)) -> Option<()> {
+    for _ in 0..*count {
+        let item = stacks.get_mut(source)?.pop()?;
+        stacks.get_mut(target)?.push(item);
+    }
+    Some(())
+}
+
+/// Moves the crates all at once, like the CrateMover 9001.
+fn move_together(stacks: &mut Stacks, (count, source, target): &(usize, usize, usize)) -> Option<()> {
+    let source_items = stacks.get_mut(source)?;
+    let items = source_items.drain(source_items.len().checked_sub(*count)?..).collect::<Vec<_>>();
+    stacks.get_mut(target)?.extend(items);
+    Some(())
+}
+
+/// The number of stacks, from the line numbering them in order from 1.
+fn parse_numbers(line: &str) -> Result<usize, Expected> {
+    let mut cursor = Cursor::new(line);
+    let mut count = 0;
+    loop {
+        cursor.tag(" ")?;
+        let start = cursor.clone();
+        if cursor.number::<usize>()? != count + 1 {
+            return Err(start.expected(format!("stack {}", count + 1)));
+        }
+        count += 1;
+        // The space after the last number is optional.
+        if cursor.is_empty() || cursor.eat(" ") && cursor.is_empty() {
+            return Ok(count);
+        }
+        cursor.tag(" ")?;
+    }
+}
+
+/// The crate on each stack in a row of the drawing, from the first stack
+/// on, with no more stacks than the `count` numbered, if known.
+fn parse_row(row: &str, count: Option<usize>) -> Result<Vec<Option<char>>, Expected> {
+    let mut cursor = Cursor::new(row);
+    let mut crates = Vec::new();
+    while !cursor.is_empty() {
+        if !crates.is_empty() {
+            cursor.tag(" ")?;
+        }
+        if count.is_some_and(|count| crates.len() == count) {
+            return Err(cursor.expected("the end of the line"));
+        }
+        if cursor.eat("   ") {
+            crates.push(None);
+            continue;
+        }
+        cursor.tag("[").map_err(|_| cursor.expected("a crate or three spaces"))?;
+        crates.push(Some(cursor.char("a crate letter", |char| char.is_ascii_alphabetic())?));
+        cursor.tag("]")?;
+    }
+    Ok(crates)
+}
+
+pub struct Day05;
+
+impl Puzzle for Day05 {
+    const DAY: &'static str = "05";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = (Stacks, Vec<(usize, usize, usize)>);
+    type Output1 = String;
+    type Output2 = String;
+    type Config = NoConfig;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        let (stacks, instrs) = input.split_once("\n\n").ok_or_else(|| context.error(0, Expected {
+            line: input.split('\n').count(),
+            column: 0,
+            expected: "a blank line after the stacks".to_owned(),
+        }))?;
+        let first_instr_line = stacks.split('\n').count() + 1;
+        // The numbers come first, as they tell how many stacks the rows may hold.
+        let (rows, numbers) = stacks.rsplit_once('\n').unwrap_or(("", stacks));
+        let count = context.check(first_instr_line - 2, parse_numbers(numbers))?;
+        // Every numbered stack exists, even those that start out empty.
+        let mut stacks = (1..=count.unwrap_or_default()).map(|number| (number, Vec::new())).collect::<Stacks>();
+        for row in context.lines(rows, 0, |row| parse_row(row, count))?.iter().rev() {
+            for (index, label) in row.iter().enumerate() {
+                if let Some(label) = label {
+                    stacks.entry(index + 1).or_default().push(*label);
+                }
+            }
+        }
+        let instrs = context.lines(instrs, first_instr_line, |instr| {
+            let mut cursor = Cursor::new(instr);
+            cursor.tag("move ")?;
+            let count = cursor.number()?;
+            cursor.tag(" from ")?;
+            let source = cursor.number()?;
+            cursor.tag(" to ")?;
+            let target = cursor.number()?;
+            cursor.end()?;
+            Ok((count, source, target))
+        })?;
+        Ok((stacks, instrs))
+    }
+
+    fn part1((stacks, instrs): &Self::Input, _config: &Self::Config) -> Result<String, Box<dyn Error>> {
+        let mut modified_stacks = stacks.clone();
+        instrs.iter().try_for_each(|instr| move_one_by_one(&mut modified_stacks, instr)).ok_or("invalid move")?;
+        Ok(top_crates(&modified_stacks))
+    }
+
+    fn part2((stacks, instrs): &Self::Input, _config: &Self::Config) -> Result<String, Box<dyn Error>> {
+        let mut modified_stacks = stacks.clone();
+        instrs.iter().try_for_each(|instr| move_together(&mut modified_stacks, instr)).ok_or("invalid move")?;
+        Ok(top_crates(&modified_stacks))
+    }
+}
+
+impl Explain for Day05 {
+    /// The top crates after every move, with either crane.
+    fn explain((stacks, instrs): &Self::Input, _config: &Self::Config) -> Result<Vec<String>, Box<dyn Error>> {
+        let (mut single, mut all) = (stacks.clone(), stacks.clone());
+        let mut lines = vec![format!("the stacks start with {} on top", top_crates(stacks))];
+        for (index, instr @ (count, source, target)) in instrs.iter().enumerate() {
+            move_one_by_one(&mut single, instr).ok_or("invalid move")?;
+            move_together(&mut all, instr).ok_or("invalid move")?;
+            lines.push(format!(
+                "move {}: {count} from {source} to {target} leaves {} on top one by one, {} all at once",
+                index + 1,
+                top_crates(&single),
+                top_crates(&all)
+            ));
+        }
+        Ok(lines)
+    }
+}
+
+impl Generate for Day05 {
+    /// A drawing of up to nine stacks followed by `size` moves, each of which
+    /// takes at most the crates its source stack holds at that point.
+    fn generate(rng: &mut InputRng, size: usize, _config: &Self::Config) -> String {
+        let mut heights = (0..rng.gen_range(2..=9)).map(|_| rng.gen_range(1..=8)).collect::<Vec<usize>>();
+        let highest = heights.iter().copied().max().unwrap_or_default();
+        let mut drawing = (0..highest)
+            .rev()
+            .map(|level| {
+                heights
+                    .iter()
+                    .map(|height| match *height > level {
+                        true => format!("[{}]", rng.gen_range('A'..='Z')),
+                        false => "   ".to_owned(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>();
+        drawing.push((1..=heights.len()).map(|number| format!(" {number} ")).collect::<Vec<_>>().join(" "));
+        let moves = (0..size).map(|_| {
+            let stacks = (0..heights.len()).filter(|stack| heights[*stack] > 0).collect::<Vec<_>>();
+            let source = *stacks.choose(rng).unwrap_or(&0);
+            let target = loop {
+                let target = rng.gen_range(0..heights.len());
+                if target != source {
+                    break target;
+                }
+            };
+            let count = rng.gen_range(1..=heights[source]);
+            heights[source] -= count;
+            heights[target] += count;
+            format!("move {count} from {} to {}", source + 1, target + 1)
+        });
+        format!("{}\n\n{}", drawing.join("\n"), moves.collect::<Vec<_>>().join("\n"))
+    }
+}
+
+/// The stacks drawn like in the input, `height` crates high.
+fn draw_stacks(stacks: &Stacks, height: usize) -> Grid<Pixel> {
+    let stacks = stacks.iter().collect::<Vec<_>>();
+    let width = (4 * stacks.len()).max(1) - 1;
+    Grid::new((0, 0), width, height + 1, ()).map(|(x, y), _| {
+        let (number, stack) = stacks[x as usize / 4];
+        let level = height.checked_sub(y as usize + 1);
+        match (x % 4, level.and_then(|level| stack.get(level))) {
+            (1, None) if level.is_none() => {
+                Pixel::new(char::from_digit(*number as u32 % 10, 10).unwrap_or(' '), Rgb::GREY)
+            }
+            (0, Some(_)) => Pixel::new('[', Rgb::GREY),
+            (1, Some(item)) => {
+                let shade = (*item as u32).saturating_sub('A' as u32) as f64 / 25.0;
+                Pixel::new(*item, Rgb::BLUE.mix(Rgb::YELLOW, shade))
+            }
+            (2, Some(_)) => Pixel::new(']', Rgb::GREY),
+            _ => Pixel::new(' ', Rgb::BLACK),
+        }
+    })
+}
+
+impl Animate for Day05 {
+    /// The stacks after every crate the CrateMover 9000 of part 1 moves.
+    fn animate((stacks, instrs): &Self::Input, _config: &Self::Config, sink: &mut FrameSink) -> Result<(), Box<dyn Error>> {
+        let height = stacks.values().map(Vec::len).sum();
+        let mut stacks = stacks.clone();
+        let mut caption = "the starting stacks".to_owned();
+        sink.push(|| Frame::new(caption.clone(), draw_stacks(&stacks, height)))?;
+        for (index, (count, source, target)) in instrs.iter().enumerate() {
+            for moved in 1..=*count {
+                let item = stacks.get_mut(source).and_then(Vec::pop).ok_or("invalid move")?;
+                stacks.get_mut(target).ok_or("invalid move")?.push(item);
+                caption = format!(
+                    "move {count} from {source} to {target}, crate {moved} of {count}, move {} of {}",
+                    index + 1,
+                    instrs.len()
+                );
+                sink.push(|| Frame::new(caption.clone(), draw_stacks(&stacks, height)))?;
+            }
+        }
+        sink.finish(|| Frame::new(caption, draw_stacks(&stacks, height)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{
+        Answers, Puzzle,
+        animate::Animate,
+        explain::Explain,
+        generate::{Generate, input_rng},
+        parse::{Mode, ParseContext},
+    };
+    use proptest::{collection::vec, prelude::*, sample::Index};
+
+    use super::Day05;
+
+    const EXAMPLE: &str = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day05::solve(EXAMPLE)?;
+        assert_eq!(answers, Answers { part1: "CMZ".to_owned(), part2: "MCD".to_owned() });
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_drawing() {
+        let error = |drawing: &str| Day05::solve(&format!("{drawing}\n\nmove 1 from 1 to 2")).unwrap_err().to_string();
+        assert_eq!(error("[A]  [B]\n 1   2"), "day 05, line 1, column 5: expected a crate or three spaces");
+        assert_eq!(error("[A] [b\n 1   2"), "day 05, line 1, column 7: expected \"]\"");
+        assert_eq!(error("[A] [B]x\n 1   2"), "day 05, line 1, column 8: expected \" \"");
+        assert_eq!(error("[A] [B] [C]\n 1   2"), "day 05, line 1, column 9: expected the end of the line");
+        assert_eq!(error("[A] [B]\n 1   3"), "day 05, line 2, column 6: expected stack 2");
+        let mut context = ParseContext::new(Day05::DAY, Mode::Lenient);
+        let (stacks, _) = Day05::parse("[A] [B]\n[C]x[D]\n 1   2\n\nmove 1 from 1 to 2", &mut context).unwrap();
+        assert_eq!(stacks.values().collect::<Vec<_>>(), [&['A'], &['B']]);
+        assert_eq!(context.skipped()[0].to_string(), "day 05, line 2, column 4: expected \" \"");
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day05::explain_str(EXAMPLE, &Default::default())?;
+        assert_eq!(explanation[0], "the stacks start with NDP on top");
+        assert_eq!(explanation[2], "move 2: 3 from 1 to 3 leaves CZ on top one by one, CD all at once");
+        assert_eq!(explanation[4], "move 4: 1 from 1 to 2 leaves CMZ on top one by one, MCD all at once");
+        Ok(())
+    }
+
+    #[test]
+    fn empty_stack_receives_crates() -> Result<(), Box<dyn Error>> {
+        let answers = Day05::solve("    [A]\n 1   2 \n\nmove 1 from 2 to 1")?;
+        assert_eq!(answers, Answers { part1: "A".to_owned(), part2: "A".to_owned() });
+        Ok(())
+    }
+
+    #[test]
+    fn animate() -> Result<(), Box<dyn Error>> {
+        let frames = Day05::record(EXAMPLE, &Default::default())?;
+        assert_eq!(frames.len(), 8);
+        let last = "\
+move 1 from 1 to 2, crate 1 of 1, move 4 of 4
+
+
+        [Z]
+        [N]
+        [D]
+[C] [M] [P]
+ 1   2   3";
+        // The stacks are drawn as high as all crates on one stack would be.
+        let ascii = frames[7].ascii();
+        assert_eq!(ascii.split('\n').map(str::trim_end).collect::<Vec<_>>().join("\n"), last);
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day05::solve(&read_to_string(Day05::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: "TPGVQPFDH".to_owned(), part2: "DMRDFRHHH".to_owned() });
+        Ok(())
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let input = Day05::generate(&mut input_rng(1), 500, &Default::default());
+        Day05::solve(&input)?;
+        Ok(())
+    }
+
+    /// Draws the stacks the way the puzzle does, with their numbers below.
+    fn draw(stacks: &[Vec<char>]) -> String {
+        let highest = stacks.iter().map(Vec::len).max().unwrap_or_default();
+        let mut lines = (0..highest)
+            .rev()
+            .map(|level| {
+                stacks
+                    .iter()
+                    .map(|stack| stack.get(level).map_or("   ".to_owned(), |item| format!("[{item}]")))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>();
+        lines.push((1..=stacks.len()).map(|number| format!(" {number} ")).collect::<Vec<_>>().join(" "));
+        lines.join("\n")
+    }
+
+    /// Turns arbitrary choices into moves that never take more crates than
+    /// a stack holds, as `(count, source, target)` with 0-based stacks.
+    fn valid_moves(stacks: &[Vec<char>], choices: &[(Index, Index, usize)]) -> Vec<(usize, usize, usize)> {
+        let mut heights = stacks.iter().map(Vec::len).collect::<Vec<_>>();
+        choices
+            .iter()
+            .filter_map(|(source, target, count)| {
+                let sources = (0..heights.len()).filter(|stack| heights[*stack] > 0).collect::<Vec<_>>();
+                if sources.is_empty() {
+                    return None;
+                }
+                let source = *source.get(&sources);
+                let target = (source + 1 + target.index(heights.len() - 1)) % heights.len();
+                let count = (*count).min(heights[source]);
+                heights[source] -= count;
+                heights[target] += count;
+                Some((count, source, target))
+            })
+            .collect()
+    }
+
+    /// Lifts the crates of every move one by one, putting them down in
+    /// reverse when the crane keeps their order.
+    fn rearrange(stacks: &[Vec<char>], moves: &[(usize, usize, usize)], keep_order: bool) -> String {
+        let mut stacks = stacks.to_vec();
+        for &(count, source, target) in moves {
+            let mut lifted = (0..count).filter_map(|_| stacks[source].pop()).collect::<Vec<_>>();
+            if keep_order {
+                lifted.reverse();
+            }
+            stacks[target].extend(lifted);
+        }
+        stacks.iter().filter_map(|stack| stack.last()).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn cranes_match_moving_one_crate_at_a_time(
+            stacks in vec(vec(prop::char::range('A', 'Z'), 0..6), 2..=9),
+            choices in vec((any::<Index>(), any::<Index>(), 1..=10usize), 1..30),
+        ) {
+            let moves = valid_moves(&stacks, &choices);
+            prop_assume!(!moves.is_empty());
+            let moves_text = moves
+                .iter()
+                .map(|(count, source, target)| format!("move {count} from {} to {}", source + 1, target + 1))
+                .collect::<Vec<_>>();
+            let answers = Day05::solve(&format!("{}\n\n{}", draw(&stacks), moves_text.join("\n"))).unwrap();
+            prop_assert_eq!(answers.part1, rearrange(&stacks, &moves, false));
+            prop_assert_eq!(answers.part2, rearrange(&stacks, &moves, true));
+        }
+    }
+}
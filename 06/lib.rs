@@ -0,0 +1,122 @@
+use std::{collections::HashSet, error::Error};
+
+use common::{
+    Puzzle,
+    config::NoConfig,
+    explain::Explain,
+    generate::{Generate, InputRng},
+    parse::ParseContext,
+};
+use rand::{Rng, seq::SliceRandom};
+
+fn find_marker(input: &[char], size: usize) -> Result<usize, Box<dyn Error>> {
+    let (index, _) = input
+        .windows(size)
+        .enumerate()
+        .find(|(_, chars)| chars.iter().collect::<HashSet<_>>().len() == size)
+        .ok_or("no match")?;
+    Ok(index + size)
+}
+
+pub struct Day06;
+
+impl Puzzle for Day06 {
+    const DAY: &'static str = "06";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = Vec<char>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Config = NoConfig;
+
+    fn parse(input: &str, _context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input.chars().collect())
+    }
+
+    fn part1(input: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        find_marker(input, 4)
+    }
+
+    fn part2(input: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        find_marker(input, 14)
+    }
+}
+
+impl Explain for Day06 {
+    /// Both markers and the character each one ends at.
+    fn explain(input: &Self::Input, _config: &Self::Config) -> Result<Vec<String>, Box<dyn Error>> {
+        [("start-of-packet", 4), ("start-of-message", 14)]
+            .into_iter()
+            .map(|(name, size)| {
+                let end = find_marker(input, size)?;
+                let marker = input[end - size..end].iter().collect::<String>();
+                Ok(format!("the {name} marker {marker} ends after character {end}"))
+            })
+            .collect()
+    }
+}
+
+impl Generate for Day06 {
+    /// A datastream of `size` characters, at least 14. The noise around the
+    /// planted packet marker uses only three letters, so neither marker can
+    /// show up before it.
+    fn generate(rng: &mut InputRng, size: usize, _config: &Self::Config) -> String {
+        let mut letters = ('a'..='z').collect::<Vec<_>>();
+        letters.shuffle(rng);
+        let (marker, noise) = letters.split_at(14);
+        let length = size.max(14);
+        let start = rng.gen_range(0..=length - 14);
+        (0..length)
+            .map(|index| match index.checked_sub(start) {
+                Some(offset) if offset < 14 => marker[offset],
+                _ => noise[rng.gen_range(0..3)],
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{
+        Answers, Puzzle,
+        explain::Explain,
+        generate::{Generate, input_rng},
+    };
+
+    use super::Day06;
+
+    const EXAMPLE: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day06::solve(EXAMPLE)?;
+        assert_eq!(answers, Answers { part1: 7, part2: 19 });
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day06::explain_str(EXAMPLE, &Default::default())?;
+        assert_eq!(explanation, [
+            "the start-of-packet marker jpqm ends after character 7",
+            "the start-of-message marker qmgbljsphdztnv ends after character 19",
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day06::solve(&read_to_string(Day06::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: 1093, part2: 3534 });
+        Ok(())
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let input = Day06::generate(&mut input_rng(1), 500, &Default::default());
+        Day06::solve(&input)?;
+        Ok(())
+    }
+}
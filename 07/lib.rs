@@ -0,0 +1,406 @@
+use std::{error::Error, collections::{BTreeMap, VecDeque}};
+use common::{
+    Puzzle,
+    explain::Explain,
+    explore::{Explore, argument, unknown_command},
+    generate::{Generate, InputRng},
+    parse::{Cursor, Expected, ParseContext},
+};
+use rand::{Rng, distributions::Alphanumeric, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+
+#[derive(Default)]
+pub struct Directory {
+    directories: BTreeMap<String, Directory>,
+    files: BTreeMap<String, usize>,
+}
+
+impl Directory {
+    fn size(&self) -> usize {
+        self.files.values().sum::<usize>() +
+        self.directories.values().map(Directory::size).sum::<usize>()
+    }
+
+    /// The directory at an absolute path such as `/a/e`.
+    fn find(&self, path: &str) -> Option<&Directory> {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(self, |directory, name| directory.directories.get(name))
+    }
+
+    /// Collects the path and size of this directory and all below it,
+    /// parents before their children, and returns this one's size.
+    fn sizes(&self, path: &str, sizes: &mut Vec<(String, usize)>) -> usize {
+        let index = sizes.len();
+        sizes.push((path.to_owned(), 0));
+        let size = self.files.values().sum::<usize>()
+            + self
+                .directories
+                .iter()
+                .map(|(name, child_dir)| child_dir.sizes(&format!("{}/{name}", path.trim_end_matches('/')), sizes))
+                .sum::<usize>();
+        sizes[index].1 = size;
+        size
+    }
+
+    fn sum_of_sizes(&self, small_size: usize) -> usize {
+        let size = self.size();
+        (if size <= small_size { size } else { 0 }) +
+        self.directories.values().map(|child_dir| child_dir.sum_of_sizes(small_size)).sum::<usize>()
+    }
+
+    fn size_of_dir_to_delete(&self, unused_space: usize, needed_space: usize) -> Option<usize> {
+        let size = self.size();
+        let child_size = self.directories.values().fold(None, |child_size, child_dir|
+            match (child_size, child_dir.size_of_dir_to_delete(unused_space, needed_space)) {
+                (None, c) => c,
+                (c, None) => c,
+                (Some(c1), Some(c2)) => Some(c1.min(c2)),
+            }
+        );
+        if unused_space + size >= needed_space {
+            if let Some(c) = child_size && c < size { child_size } else { Some(size) }
+        } else {
+            child_size
+        }
+        
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The size of the disk.
+    pub disk_space: usize,
+    /// How much space the update needs.
+    pub needed_space: usize,
+    /// The largest size of a directory counted in part 1.
+    pub small_size: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            disk_space: 70_000_000,
+            needed_space: 30_000_000,
+            small_size: 100_000,
+        }
+    }
+}
+
+pub struct Day07;
+
+impl Puzzle for Day07 {
+    const DAY: &'static str = "07";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = Directory;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Config = Config;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        let mut input_lines = input.split('\n').map(ToOwned::to_owned).enumerate().collect::<VecDeque<_>>();
+        // The session starts in the root directory, which the tree stands for.
+        if let Some((line, first_line)) = input_lines.pop_front() {
+            let root = parse_command(&first_line).and_then(|command| match command {
+                Command::Cd("/") => Ok(()),
+                _ => Err(Expected::line("`$ cd /`")),
+            });
+            context.check(line, root)?;
+        }
+        let mut root_directory = Directory::default();
+        handle_cd(&mut root_directory, &mut input_lines, context, 0)?;
+        Ok(root_directory)
+    }
+
+    fn part1(root_directory: &Self::Input, config: &Config) -> Result<usize, Box<dyn Error>> {
+        Ok(root_directory.sum_of_sizes(config.small_size))
+    }
+
+    fn part2(root_directory: &Self::Input, config: &Config) -> Result<usize, Box<dyn Error>> {
+        let unused_space = config.disk_space.checked_sub(root_directory.size()).ok_or("the files do not fit on the disk")?;
+        let directory_to_delete = root_directory
+            .size_of_dir_to_delete(unused_space, config.needed_space)
+            .ok_or("no directory found")?;
+        Ok(directory_to_delete)
+    }
+}
+
+impl Explain for Day07 {
+    /// The size of every directory, which of them part 1 counts, and which
+    /// one part 2 deletes.
+    fn explain(root_directory: &Self::Input, config: &Config) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut sizes = Vec::new();
+        let used_space = root_directory.sizes("/", &mut sizes);
+        let mut lines = sizes.iter().map(|(path, size)| format!("{path} holds {size}")).collect::<Vec<_>>();
+        let small = sizes.iter().filter(|(_, size)| *size <= config.small_size).collect::<Vec<_>>();
+        lines.push(format!(
+            "{} directories hold at most {}, together {}",
+            small.len(),
+            config.small_size,
+            small.iter().map(|(_, size)| size).sum::<usize>()
+        ));
+        let unused_space = config.disk_space.checked_sub(used_space).ok_or("the files do not fit on the disk")?;
+        let missing_space = config.needed_space.saturating_sub(unused_space);
+        lines.push(format!("{unused_space} is free, so {missing_space} more is needed"));
+        let (path, size) = sizes
+            .iter()
+            .filter(|(_, size)| *size >= missing_space)
+            .min_by_key(|(_, size)| *size)
+            .ok_or("no directory found")?;
+        lines.push(format!("deleting {path} frees {size}"));
+        Ok(lines)
+    }
+}
+
+impl Explore for Day07 {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        ("size PATH", "the total size of the directory at PATH, e.g. /a/e"),
+        ("ls PATH", "the directories and files in the directory at PATH, with their sizes"),
+    ];
+
+    fn query(root_directory: &Self::Input, _config: &Config, command: &str, arguments: &[&str]) -> Result<Vec<String>, Box<dyn Error>> {
+        let find = |path: &str| root_directory.find(path).ok_or_else(|| format!("no directory {path}"));
+        match command {
+            "size" => Ok(vec![find(argument::<String>(arguments, "a path")?.as_str())?.size().to_string()]),
+            "ls" => {
+                let directory = find(argument::<String>(arguments, "a path")?.as_str())?;
+                let directories = directory.directories.iter().map(|(name, child_dir)| format!("dir {name} ({})", child_dir.size()));
+                let files = directory.files.iter().map(|(name, size)| format!("{size} {name}"));
+                Ok(directories.chain(files).collect())
+            }
+            _ => Err(unknown_command(command)),
+        }
+    }
+}
+
+type InputLines = VecDeque<(usize, String)>;
+
+/// How deeply directories may nest, so that walking the tree cannot
+/// overflow the stack.
+const MAX_DEPTH: usize = 100;
+
+/// A command line such as `$ cd a` or `$ ls`.
+#[derive(Debug, PartialEq, Eq)]
+enum Command<'t> {
+    Cd(&'t str),
+    Ls,
+}
+
+fn parse_command(text: &str) -> Result<Command<'_>, Expected> {
+    let mut cursor = Cursor::new(text);
+    cursor.tag("$ ").map_err(|_| cursor.expected("a command"))?;
+    let command = match cursor.one_of(&["cd ", "ls"]).map_err(|_| cursor.expected("`cd` or `ls`"))? {
+        "ls" => Command::Ls,
+        _ => Command::Cd(cursor.line("a directory")?),
+    };
+    cursor.end()?;
+    Ok(command)
+}
+
+/// The name and size of a file listed by `ls`.
+fn parse_file(text: &str) -> Result<(&str, usize), Expected> {
+    let mut cursor = Cursor::new(text);
+    if !cursor.rest().starts_with(|char: char| char.is_ascii_digit()) {
+        return Err(cursor.expected("a directory or a file size and name"));
+    }
+    let size = cursor.number::<u32>()? as usize;
+    cursor.tag(" ")?;
+    let name = cursor.line("a file name")?;
+    Ok((name, size))
+}
+
+fn handle_cd(directory: &mut Directory, input_lines: &mut InputLines, context: &mut ParseContext, depth: usize) -> Result<(), Box<dyn Error>> {
+    while !input_lines.is_empty() {
+        let (line, input_line) = input_lines.pop_front().ok_or("no input line")?;
+        let Some(command) = context.check(line, parse_command(&input_line))? else {
+            continue;
+        };
+        match command {
+            Command::Ls =>
+                handle_ls(directory, input_lines, context)?,
+            Command::Cd("..") => break,
+            Command::Cd(name) => {
+                if depth == MAX_DEPTH {
+                    return Err(context.error(line, Expected::line(format!("directories nested at most {MAX_DEPTH} deep"))).into());
+                }
+                let child_dir = directory.directories.entry(name.to_owned()).or_default();
+                handle_cd(child_dir, input_lines, context, depth + 1)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn handle_ls(directory: &mut Directory, input_lines: &mut InputLines, context: &mut ParseContext) -> Result<(), Box<dyn Error>> {
+    while let Some((_, front)) = input_lines.front() && !front.starts_with("$") {
+        let (line, input_line) = input_lines.pop_front().ok_or("no input line")?;
+        if input_line.starts_with("dir ") {
+            continue;
+        }
+        if let Some((file_name, file_length)) = context.check(line, parse_file(&input_line))? {
+            directory.files.entry(file_name.to_owned()).or_insert(file_length);
+        }
+    }
+    Ok(())
+}
+
+impl Generate for Day07 {
+    /// A terminal session exploring a random tree of `size` files, sized so
+    /// that all of them fit on the disk.
+    fn generate(rng: &mut InputRng, size: usize, config: &Self::Config) -> String {
+        let mut tree = vec![GeneratedDirectory::default()];
+        let largest_file = (config.disk_space / size.max(1)).max(1);
+        for _ in 0..size {
+            let mut parent = rng.gen_range(0..tree.len());
+            if rng.gen_bool(0.3) {
+                let name = loop {
+                    let name = random_name(rng);
+                    if tree[parent].directories.iter().all(|(sibling, _)| *sibling != name) {
+                        break name;
+                    }
+                };
+                let child = tree.len();
+                tree[parent].directories.push((name, child));
+                tree.push(GeneratedDirectory::default());
+                parent = child;
+            }
+            let name = format!("{}.{}", random_name(rng), tree[parent].files.len());
+            tree[parent].files.push((name, rng.gen_range(1..=largest_file)));
+        }
+        let mut lines = vec!["$ cd /".to_owned()];
+        write_session(&tree, 0, rng, &mut lines);
+        lines.join("\n")
+    }
+}
+
+#[derive(Default)]
+struct GeneratedDirectory {
+    directories: Vec<(String, usize)>,
+    files: Vec<(String, usize)>,
+}
+
+fn random_name(rng: &mut InputRng) -> String {
+    let length = rng.gen_range(1..=8);
+    rng.sample_iter(Alphanumeric).take(length).map(|byte| char::from(byte).to_ascii_lowercase()).collect()
+}
+
+fn write_session(tree: &[GeneratedDirectory], index: usize, rng: &mut InputRng, lines: &mut Vec<String>) {
+    lines.push("$ ls".to_owned());
+    let mut entries = tree[index]
+        .directories
+        .iter()
+        .map(|(name, _)| format!("dir {name}"))
+        .chain(tree[index].files.iter().map(|(name, size)| format!("{size} {name}")))
+        .collect::<Vec<_>>();
+    entries.shuffle(rng);
+    lines.extend(entries);
+    for (name, child) in &tree[index].directories {
+        lines.push(format!("$ cd {name}"));
+        write_session(tree, *child, rng, lines);
+        lines.push("$ cd ..".to_owned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{
+        Answers, Puzzle,
+        explain::Explain,
+        explore::Explore,
+        generate::{Generate, input_rng},
+        parse::{Mode, ParseContext},
+    };
+
+    use super::Day07;
+
+    const EXAMPLE: &str = "\
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day07::solve(EXAMPLE)?;
+        assert_eq!(answers, Answers { part1: 95437, part2: 24933642 });
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_commands() -> Result<(), Box<dyn Error>> {
+        let input = EXAMPLE.replace("$ cd e", "$ rm e");
+        let error = Day07::solve(&input).unwrap_err();
+        assert_eq!(error.to_string(), "day 07, line 13, column 3: expected `cd` or `ls`");
+        let error = Day07::solve(&EXAMPLE.replacen("$ cd /", "$ cd a", 1)).unwrap_err();
+        assert_eq!(error.to_string(), "day 07, line 1, column 1: expected `$ cd /`");
+        let mut context = ParseContext::new(Day07::DAY, Mode::Lenient);
+        let root = Day07::parse(&input.replace("$ cd ..\n$ cd ..", "$ cd .."), &mut context)?;
+        assert_eq!(root.size(), 48381165);
+        let skipped = context.skipped().iter().map(|error| error.line).collect::<Vec<_>>();
+        assert_eq!(skipped, [13]);
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day07::explain_str(EXAMPLE, &Default::default())?;
+        assert_eq!(explanation, [
+            "/ holds 48381165",
+            "/a holds 94853",
+            "/a/e holds 584",
+            "/d holds 24933642",
+            "2 directories hold at most 100000, together 95437",
+            "21618835 is free, so 8381165 more is needed",
+            "deleting /d frees 24933642",
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn explore() -> Result<(), Box<dyn Error>> {
+        let root_directory = Day07::parse(EXAMPLE, &mut ParseContext::new(Day07::DAY, Mode::Strict))?;
+        let query = |command, arguments: &[&str]| Day07::query(&root_directory, &Default::default(), command, arguments);
+        assert_eq!(query("size", &["/a/e"])?, ["584"]);
+        assert_eq!(query("size", &["/"])?, ["48381165"]);
+        assert_eq!(query("ls", &["/a"])?, ["dir e (584)", "29116 f", "2557 g", "62596 h.lst"]);
+        assert_eq!(query("size", &["/x"]).unwrap_err().to_string(), "no directory /x");
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day07::solve(&read_to_string(Day07::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: 1325919, part2: 2050735 });
+        Ok(())
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let input = Day07::generate(&mut input_rng(1), 500, &Default::default());
+        Day07::solve(&input)?;
+        Ok(())
+    }
+}
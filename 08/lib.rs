@@ -0,0 +1,234 @@
+use std::error::Error;
+
+use common::{
+    Puzzle,
+    config::NoConfig,
+    explain::Explain,
+    generate::{Generate, InputRng},
+    grid::{DIRECTIONS_4, Grid, Point},
+    parse::{Expected, ParseContext},
+    visualize::{Frame, Pixel, Rgb, Visualize},
+};
+use rand::Rng;
+
+pub struct Day08;
+
+impl Puzzle for Day08 {
+    const DAY: &'static str = "08";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = Grid<i8>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Config = NoConfig;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        Grid::parse(input, |_, char| {
+            char.to_digit(10)
+                .map(|height| height as i8)
+                .ok_or_else(|| Expected::line("a tree height"))
+        })
+        .map_err(|expected| context.error(0, expected).into())
+    }
+
+    fn part1(tree_grid: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        let visible_from_outside = tree_grid
+            .iter()
+            .filter(|(coord, _)| is_visible_any_direction(tree_grid, *coord))
+            .count();
+        Ok(visible_from_outside)
+    }
+
+    fn part2(tree_grid: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        let largest_scenic_score = tree_grid
+            .iter()
+            .map(|(coord, _)| scenic_score(tree_grid, coord))
+            .max()
+            .unwrap_or_default();
+        Ok(largest_scenic_score)
+    }
+}
+
+fn is_visible(tree_grid: &Grid<i8>, coord: Point, direction: Point) -> bool {
+    let size = tree_grid[coord];
+    tree_grid
+        .ray(coord, direction)
+        .map(|(_, neighbor)| *neighbor)
+        .max()
+        .unwrap_or(-1)
+        < size
+}
+
+fn is_visible_any_direction(tree_grid: &Grid<i8>, coord: Point) -> bool {
+    DIRECTIONS_4
+        .into_iter()
+        .any(|direction| is_visible(tree_grid, coord, direction))
+}
+
+fn viewing_distance(tree_grid: &Grid<i8>, coord: Point, direction: Point) -> usize {
+    let size = tree_grid[coord];
+    tree_grid
+        .ray(coord, direction)
+        .fold(Default::default(), |(found_tree, distance), (_, neighbor)| {
+            if found_tree {
+                (true, distance)
+            } else {
+                (*neighbor >= size, distance + 1)
+            }
+        })
+        .1
+}
+
+fn scenic_score(tree_grid: &Grid<i8>, coord: Point) -> usize {
+    DIRECTIONS_4
+        .into_iter()
+        .map(|direction| viewing_distance(tree_grid, coord, direction))
+        .product()
+}
+
+/// The names of `DIRECTIONS_4`, in the same order.
+const DIRECTION_NAMES: [&str; 4] = ["right", "left", "down", "up"];
+
+impl Explain for Day08 {
+    /// How many trees on the edge are visible, the directions every visible
+    /// tree inside the forest is seen from, and the view of the best tree.
+    fn explain(tree_grid: &Self::Input, _config: &Self::Config) -> Result<Vec<String>, Box<dyn Error>> {
+        let (edge, inside) = tree_grid
+            .iter()
+            .partition::<Vec<_>, _>(|(coord, _)| tree_grid.neighbors4(*coord).count() < 4);
+        let mut lines = vec![format!("all {} trees on the edge are visible", edge.len())];
+        lines.extend(inside.into_iter().filter_map(|(coord, height)| {
+            let directions = DIRECTIONS_4
+                .into_iter()
+                .zip(DIRECTION_NAMES)
+                .filter(|(direction, _)| is_visible(tree_grid, coord, *direction))
+                .map(|(_, name)| name)
+                .collect::<Vec<_>>();
+            (!directions.is_empty()).then(|| format!("the {height} at {coord:?} is visible from {}", directions.join(", ")))
+        }));
+        if let Some((coord, height)) = tree_grid.iter().max_by_key(|(coord, _)| scenic_score(tree_grid, *coord)) {
+            let distances = DIRECTIONS_4
+                .into_iter()
+                .zip(DIRECTION_NAMES)
+                .map(|(direction, name)| format!("{} {name}", viewing_distance(tree_grid, coord, direction)))
+                .collect::<Vec<_>>();
+            lines.push(format!(
+                "the {height} at {coord:?} sees {}, a scenic score of {}",
+                distances.join(", "),
+                scenic_score(tree_grid, coord)
+            ));
+        }
+        Ok(lines)
+    }
+}
+
+impl Generate for Day08 {
+    /// A square forest `size` trees wide, with short trees more common than tall ones.
+    fn generate(rng: &mut InputRng, size: usize, _config: &Self::Config) -> String {
+        (0..size.max(1))
+            .map(|_| {
+                (0..size.max(1))
+                    .map(|_| char::from(b'0' + rng.gen_range(0..=9).min(rng.gen_range(0..=9))))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Visualize for Day08 {
+    /// The trees visible from outside the forest as their heights, greener
+    /// the taller they are, the hidden ones as dots and the tree with the
+    /// best view as a star.
+    fn visualize(tree_grid: &Self::Input, _config: &Self::Config) -> Result<Frame, Box<dyn Error>> {
+        let best = tree_grid.iter().max_by_key(|(coord, _)| scenic_score(tree_grid, *coord));
+        let best_coord = best.map(|(coord, _)| coord);
+        let mut visible_count = 0;
+        let forest = tree_grid.map(|coord, height| {
+            let visible = is_visible_any_direction(tree_grid, coord);
+            visible_count += visible as usize;
+            if Some(coord) == best_coord {
+                Pixel::new('*', Rgb::RED)
+            } else if visible {
+                let glyph = char::from(b'0' + *height as u8);
+                Pixel::new(glyph, Rgb::DARK_GREY.mix(Rgb::GREEN, (*height + 1) as f64 / 10.0))
+            } else {
+                Pixel::new('.', Rgb::DARK_GREY)
+            }
+        });
+        let caption = match best_coord {
+            Some(coord) => format!(
+                "{visible_count} trees visible, the best scenic score is {} at {coord:?}",
+                scenic_score(tree_grid, coord)
+            ),
+            None => "no trees".to_owned(),
+        };
+        Ok(Frame::new(caption, forest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{
+        Answers, Puzzle,
+        explain::Explain,
+        generate::{Generate, input_rng},
+        visualize::Visualize,
+    };
+
+    use super::Day08;
+
+    const EXAMPLE: &str = "\
+30373
+25512
+65332
+33549
+35390";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day08::solve(EXAMPLE)?;
+        assert_eq!(answers, Answers { part1: 21, part2: 8 });
+        Ok(())
+    }
+
+    #[test]
+    fn visualize() -> Result<(), Box<dyn Error>> {
+        let frame = Day08::draw(EXAMPLE, &Default::default())?;
+        let forest = "\
+21 trees visible, the best scenic score is 8 at (2, 3)
+30373
+255.2
+65.32
+3.*.9
+35390";
+        assert_eq!(frame.ascii(), forest);
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day08::explain_str(EXAMPLE, &Default::default())?;
+        assert_eq!(explanation.len(), 7);
+        assert_eq!(explanation[0], "all 16 trees on the edge are visible");
+        assert_eq!(explanation[1], "the 5 at (1, 1) is visible from left, up");
+        assert_eq!(explanation[6], "the 5 at (2, 3) sees 2 right, 2 left, 1 down, 2 up, a scenic score of 8");
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day08::solve(&read_to_string(Day08::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: 1546, part2: 519064 });
+        Ok(())
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let input = Day08::generate(&mut input_rng(1), 50, &Default::default());
+        Day08::solve(&input)?;
+        Ok(())
+    }
+}
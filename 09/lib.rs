@@ -0,0 +1,309 @@
+use std::error::Error;
+
+use common::{
+    Puzzle,
+    config::NoConfig,
+    animate::{Animate, FrameSink},
+    explain::Explain,
+    generate::{Generate, InputRng},
+    grid::{Grid, Point, PointSet, add},
+    parse::{Cursor, ParseContext},
+    visualize::{Frame, Pixel, Rgb, Visualize},
+};
+use rand::{Rng, seq::SliceRandom};
+
+fn perform_move<const N: usize>(rope: &mut [Point; N], direction: Point) -> Option<&Point> {
+    rope[0] = add(rope[0], direction);
+
+    for i in 0..N - 1 {
+        let diff = (rope[i].0 - rope[i + 1].0, rope[i].1 - rope[i + 1].1);
+        if diff.0.abs() == 2 || diff.1.abs() == 2 {
+            rope[i + 1] = add(rope[i + 1], (diff.0.signum(), diff.1.signum()));
+        } else {
+            return None;
+        }
+    }
+
+    Some(&rope[N - 1])
+}
+
+/// A set of points large enough to hold every knot: no knot ever leaves the area the head has visited.
+fn head_area(rope_moves: &[(Point, usize)]) -> PointSet {
+    let (top_left, bottom_right, _) = rope_moves.iter().fold(
+        ((0, 0), (0, 0), (0, 0)),
+        |(top_left, bottom_right, head), &((dx, dy), amount)| {
+            let head = add(head, (dx * amount as isize, dy * amount as isize));
+            (
+                (top_left.0.min(head.0), top_left.1.min(head.1)),
+                (bottom_right.0.max(head.0), bottom_right.1.max(head.1)),
+                head,
+            )
+        },
+    );
+    PointSet::spanning(top_left, bottom_right)
+}
+
+/// The positions the tail of a rope with `N` knots visits.
+fn tail_trail<const N: usize>(rope_moves: &[(Point, usize)]) -> PointSet {
+    let mut rope = [Default::default(); N];
+    let mut tail_positions = head_area(rope_moves);
+    tail_positions.insert((0, 0));
+
+    for (direction, amount) in rope_moves {
+        for _ in 0..*amount {
+            if let Some(tail) = perform_move(&mut rope, *direction) {
+                tail_positions.insert(*tail);
+            }
+        }
+    }
+
+    tail_positions
+}
+
+fn count_tail_positions<const N: usize>(rope_moves: &[(Point, usize)]) -> Result<usize, Box<dyn Error>> {
+    Ok(tail_trail::<N>(rope_moves).len())
+}
+
+pub struct Day09;
+
+impl Puzzle for Day09 {
+    const DAY: &'static str = "09";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = Vec<(Point, usize)>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Config = NoConfig;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(context.lines(input, 0, |rope_move| {
+            let mut cursor = Cursor::new(rope_move);
+            let direction = match cursor.one_of(&["R", "L", "U", "D"])? {
+                "R" => (1, 0),
+                "L" => (-1, 0),
+                "U" => (0, -1),
+                _ => (0, 1),
+            };
+            cursor.tag(" ")?;
+            // Small enough that the head's position cannot overflow.
+            let amount = cursor.number::<u32>()? as usize;
+            cursor.end()?;
+            Ok((direction, amount))
+        })?)
+    }
+
+    fn part1(rope_moves: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        count_tail_positions::<2>(rope_moves)
+    }
+
+    fn part2(rope_moves: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        count_tail_positions::<10>(rope_moves)
+    }
+}
+
+impl Explain for Day09 {
+    /// Where the head and the tails of both ropes end up after every move,
+    /// and how many positions each tail has visited so far.
+    fn explain(rope_moves: &Self::Input, _config: &Self::Config) -> Result<Vec<String>, Box<dyn Error>> {
+        let (mut short_rope, mut long_rope) = ([(0, 0); 2], [(0, 0); 10]);
+        let (mut short_trail, mut long_trail) = (head_area(rope_moves), head_area(rope_moves));
+        short_trail.insert((0, 0));
+        long_trail.insert((0, 0));
+        let mut lines = Vec::new();
+        for (index, (direction, amount)) in rope_moves.iter().enumerate() {
+            for _ in 0..*amount {
+                if let Some(tail) = perform_move(&mut short_rope, *direction) {
+                    short_trail.insert(*tail);
+                }
+                if let Some(tail) = perform_move(&mut long_rope, *direction) {
+                    long_trail.insert(*tail);
+                }
+            }
+            lines.push(format!(
+                "move {}: the head is at {:?}, the short tail at {:?} after {} positions, the long tail at {:?} after {}",
+                index + 1,
+                short_rope[0],
+                short_rope[1],
+                short_trail.len(),
+                long_rope[9],
+                long_trail.len()
+            ));
+        }
+        Ok(lines)
+    }
+}
+
+impl Generate for Day09 {
+    /// One move of the head per line.
+    fn generate(rng: &mut InputRng, size: usize, _config: &Self::Config) -> String {
+        (0..size)
+            .map(|_| format!("{} {}", ["R", "L", "U", "D"].choose(rng).unwrap_or(&"R"), rng.gen_range(1..=20)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The trails of the tails of both ropes: `+` where only the short rope's
+/// tail went, `x` where only the long one's went, and `#` where both did.
+fn draw_trails(short_trail: &Grid<bool>, long_trail: &Grid<bool>) -> Grid<Pixel> {
+    short_trail.map(|coord, short| match (coord, short, long_trail[coord]) {
+        ((0, 0), _, _) => Pixel::new('s', Rgb::RED),
+        (_, true, true) => Pixel::new('#', Rgb::YELLOW),
+        (_, true, false) => Pixel::new('+', Rgb::BLUE),
+        (_, false, true) => Pixel::new('x', Rgb::GREEN),
+        (_, false, false) => Pixel::new('.', Rgb::DARK_GREY),
+    })
+}
+
+/// The grid of a trail, which drawing it needs.
+fn trail_grid(trail: &PointSet) -> Result<&Grid<bool>, Box<dyn Error>> {
+    Ok(trail.grid().ok_or("the rope moves too far to draw")?)
+}
+
+impl Visualize for Day09 {
+    /// The trails of the tails of both ropes over the area the head covers.
+    fn visualize(rope_moves: &Self::Input, _config: &Self::Config) -> Result<Frame, Box<dyn Error>> {
+        let short_trail = tail_trail::<2>(rope_moves);
+        let long_trail = tail_trail::<10>(rope_moves);
+        let caption = format!(
+            "the tail visits {} positions with 2 knots and {} with 10",
+            short_trail.len(),
+            long_trail.len()
+        );
+        Ok(Frame::new(caption, draw_trails(trail_grid(&short_trail)?, trail_grid(&long_trail)?)))
+    }
+}
+
+/// The trails of both tails with the knots of the long rope drawn over them.
+fn draw_rope(short_trail: &Grid<bool>, long_trail: &Grid<bool>, long_rope: &[Point; 10]) -> Grid<Pixel> {
+    let mut picture = draw_trails(short_trail, long_trail);
+    for (index, knot) in long_rope.iter().enumerate().rev() {
+        let glyph = if index == 0 { 'H' } else { char::from(b'0' + index as u8) };
+        picture[*knot] = Pixel::new(glyph, Rgb::WHITE);
+    }
+    picture
+}
+
+impl Animate for Day09 {
+    /// Both ropes after every step of the head, showing the knots of the
+    /// long rope over the trails of both tails.
+    fn animate(rope_moves: &Self::Input, _config: &Self::Config, sink: &mut FrameSink) -> Result<(), Box<dyn Error>> {
+        let (mut short_rope, mut long_rope) = ([(0, 0); 2], [(0, 0); 10]);
+        let head_area = head_area(rope_moves);
+        let (mut short_trail, mut long_trail) = (trail_grid(&head_area)?.clone(), trail_grid(&head_area)?.clone());
+        short_trail[(0, 0)] = true;
+        long_trail[(0, 0)] = true;
+        sink.push(|| Frame::new("step 0", draw_rope(&short_trail, &long_trail, &long_rope)))?;
+        let steps = rope_moves.iter().flat_map(|(direction, amount)| (0..*amount).map(move |_| *direction));
+        let mut step_count = 0;
+        for direction in steps {
+            if let Some(tail) = perform_move(&mut short_rope, direction) {
+                short_trail[*tail] = true;
+            }
+            if let Some(tail) = perform_move(&mut long_rope, direction) {
+                long_trail[*tail] = true;
+            }
+            step_count += 1;
+            sink.push(|| Frame::new(format!("step {step_count}"), draw_rope(&short_trail, &long_trail, &long_rope)))?;
+        }
+        sink.finish(|| Frame::new(format!("step {step_count}"), draw_rope(&short_trail, &long_trail, &long_rope)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{
+        Answers, Puzzle,
+        animate::Animate,
+        explain::Explain,
+        generate::{Generate, input_rng},
+        visualize::Visualize,
+    };
+
+    use super::Day09;
+
+    const EXAMPLE: &str = "\
+R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day09::solve(EXAMPLE)?;
+        assert_eq!(answers, Answers { part1: 13, part2: 1 });
+        Ok(())
+    }
+
+    #[test]
+    fn visualize() -> Result<(), Box<dyn Error>> {
+        let frame = Day09::draw(EXAMPLE, &Default::default())?;
+        let trails = "\
+the tail visits 13 positions with 2 knots and 1 with 10
+..++..
+...++.
+.++++.
+....+.
+s+++..";
+        assert_eq!(frame.ascii(), trails);
+        Ok(())
+    }
+
+    #[test]
+    fn animate() -> Result<(), Box<dyn Error>> {
+        let frames = Day09::record(EXAMPLE, &Default::default())?;
+        assert_eq!(frames.len(), 25);
+        let first_move = "\
+step 4
+......
+......
+......
+......
+4321H.";
+        assert_eq!(frames[4].ascii(), first_move);
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day09::explain_str(EXAMPLE, &Default::default())?;
+        assert_eq!(
+            explanation[0],
+            "move 1: the head is at (4, 0), the short tail at (3, 0) after 4 positions, the long tail at (0, 0) after 1"
+        );
+        assert_eq!(
+            explanation[7],
+            "move 8: the head is at (2, -2), the short tail at (1, -2) after 13 positions, the long tail at (0, 0) after 1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn long_moves() -> Result<(), Box<dyn Error>> {
+        let answers = Day09::solve("R 5000\nU 5000")?;
+        assert_eq!(answers, Answers { part1: 9999, part2: 9983 });
+        let error = Day09::draw("R 5000\nU 5000", &Default::default()).unwrap_err();
+        assert_eq!(error.to_string(), "the rope moves too far to draw");
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day09::solve(&read_to_string(Day09::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: 6498, part2: 2531 });
+        Ok(())
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let input = Day09::generate(&mut input_rng(1), 500, &Default::default());
+        Day09::solve(&input)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,484 @@
+use std::{error::Error, fmt::{self, Display, Formatter}};
+
+use common::{
+    Puzzle,
+    explain::Explain,
+    generate::{Generate, InputRng},
+    animate::{Animate, FrameSink},
+    grid::Grid,
+    parse::{Cursor, Expected, ParseContext},
+    visualize::{Frame, Pixel, Rgb, Visualize},
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+pub enum Command {
+    Noop,
+    AddX(isize),
+}
+
+impl Command {
+    fn parse(input: &str) -> Result<Self, Expected> {
+        let mut cursor = Cursor::new(input);
+        let command = match cursor.one_of(&["noop", "addx "])? {
+            "noop" => Self::Noop,
+            _ => Self::AddX(cursor.signed::<i32>()? as isize),
+        };
+        cursor.end()?;
+        Ok(command)
+    }
+
+    fn cycle_count(&self) -> isize {
+        match self {
+            Self::Noop => 1,
+            Self::AddX(_) => 2,
+        }
+    }
+
+    fn apply(&self, x: &mut isize) {
+        if let Self::AddX(param) = self {
+            *x += param;
+        }
+    }
+}
+
+/// The pixels drawn on the CRT screen, row by row.
+pub struct Crt {
+    width: usize,
+    pixels: Vec<char>,
+}
+
+impl Display for Crt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let lines = self.pixels.chunks(self.width).map(String::from_iter).collect::<Vec<_>>();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The first cycle whose signal strength is summed.
+    pub first_cycle: isize,
+    /// The last cycle whose signal strength may be summed.
+    pub last_cycle: isize,
+    /// How many cycles apart the summed signal strengths are.
+    pub cycle_step: usize,
+    pub screen_width: usize,
+    pub screen_height: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            first_cycle: 20,
+            last_cycle: 220,
+            cycle_step: 40,
+            screen_width: 40,
+            screen_height: 6,
+        }
+    }
+}
+
+/// Checks the settings that `execute` and `draw_screen` divide by.
+fn validate(config: &Config) -> Result<(), Box<dyn Error>> {
+    if config.cycle_step == 0 || config.screen_width == 0 {
+        return Err("the cycle step and the screen width must be at least 1".into());
+    }
+    Ok(())
+}
+
+/// Whether the signal strength during `cycle` is summed.
+fn sampled(config: &Config, cycle: isize) -> bool {
+    (config.first_cycle..=config.last_cycle).contains(&cycle) && cycle.abs_diff(config.first_cycle).is_multiple_of(config.cycle_step)
+}
+
+/// Runs the program, returning the summed signal strength and the drawn CRT.
+/// After every cycle, `on_cycle` sees the cycle, the sprite position during
+/// it and the pixels drawn so far.
+fn execute<F>(commands: &[Command], config: &Config, mut on_cycle: F) -> Result<(isize, Crt), Box<dyn Error>>
+where
+    F: FnMut(isize, isize, &[char]) -> Result<(), Box<dyn Error>>,
+{
+    validate(config)?;
+    let (mut cycle, mut x, mut signal_strength): (isize, isize, isize) = (1, 1, 0);
+    let width = config.screen_width as isize;
+    let mut crt = vec![' '; config.screen_width * config.screen_height];
+
+    for command in commands {
+        for cycle_offset in 1..=command.cycle_count() {
+            if sampled(config, cycle) {
+                signal_strength = x.checked_mul(cycle).and_then(|strength| signal_strength.checked_add(strength)).ok_or("the signal strength overflows")?;
+            }
+            if (x - 1..=x + 1).contains(&((cycle - 1) % width)) && let Some(pixel) = crt.get_mut((cycle - 1) as usize) {
+                *pixel = '█';
+            }
+            on_cycle(cycle, x, &crt)?;
+            if cycle_offset == command.cycle_count() {
+                command.apply(&mut x);
+            }
+            cycle += 1;
+        }
+    }
+
+    Ok((signal_strength, Crt { width: config.screen_width, pixels: crt }))
+}
+
+pub struct Day10;
+
+impl Puzzle for Day10 {
+    const DAY: &'static str = "10";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = Vec<Command>;
+    type Output1 = isize;
+    type Output2 = Crt;
+    type Config = Config;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(context.lines(input, 0, Command::parse)?)
+    }
+
+    fn part1(commands: &Self::Input, config: &Config) -> Result<isize, Box<dyn Error>> {
+        let (signal_strength, _) = execute(commands, config, |_, _, _| Ok(()))?;
+        Ok(signal_strength)
+    }
+
+    fn part2(commands: &Self::Input, config: &Config) -> Result<Crt, Box<dyn Error>> {
+        let (_, crt) = execute(commands, config, |_, _, _| Ok(()))?;
+        Ok(crt)
+    }
+}
+
+impl Explain for Day10 {
+    /// The register during every cycle whose signal strength is summed.
+    fn explain(commands: &Self::Input, config: &Config) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut lines = Vec::new();
+        let mut total = 0;
+        let (signal_strength, _) = execute(commands, config, |cycle, x, _| {
+            if sampled(config, cycle) {
+                total += cycle * x;
+                lines.push(format!("during cycle {cycle}, x is {x}: a signal strength of {}, {total} so far", cycle * x));
+            }
+            Ok(())
+        })?;
+        lines.push(format!("the signal strengths sum to {signal_strength}"));
+        Ok(lines)
+    }
+}
+
+impl Generate for Day10 {
+    /// A program of `size` instructions, with `addx` twice as common as
+    /// `noop`, that keeps the sprite on the screen.
+    fn generate(rng: &mut InputRng, size: usize, config: &Self::Config) -> String {
+        let mut x = 1;
+        (0..size)
+            .map(|_| match rng.gen_range(0..3) {
+                0 => "noop".to_owned(),
+                _ => {
+                    let target = rng.gen_range(0..config.screen_width.max(1) as isize);
+                    let value = target - x;
+                    x = target;
+                    format!("addx {value}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The first `drawn` pixels of the screen, lit ones in green. If the
+/// sprite is given, it is shown in the row being drawn and the last drawn
+/// pixel is highlighted.
+fn draw_screen(crt: &[char], config: &Config, drawn: usize, sprite: Option<isize>) -> Grid<Pixel> {
+    let width = config.screen_width as isize;
+    let current_row = (drawn.saturating_sub(1) / config.screen_width) as isize;
+    Grid::new((0, 0), config.screen_width, config.screen_height, ()).map(|(x, y), _| {
+        let index = (y * width + x) as usize;
+        let pixel = match crt[index] {
+            _ if index >= drawn => match sprite {
+                Some(sprite) if y == current_row && (sprite - 1..=sprite + 1).contains(&x) => {
+                    Pixel::new('=', Rgb::YELLOW)
+                }
+                _ => Pixel::new(' ', Rgb::BLACK),
+            },
+            ' ' => Pixel::new('.', Rgb::DARK_GREY),
+            _ => Pixel::new('#', Rgb::GREEN),
+        };
+        match sprite {
+            Some(_) if index + 1 == drawn => Pixel { color: Rgb::RED, ..pixel },
+            _ => pixel,
+        }
+    })
+}
+
+impl Visualize for Day10 {
+    /// The CRT screen, with the lit pixels in green.
+    fn visualize(commands: &Self::Input, config: &Config) -> Result<Frame, Box<dyn Error>> {
+        let (signal_strength, crt) = execute(commands, config, |_, _, _| Ok(()))?;
+        let screen = draw_screen(&crt.pixels, config, crt.pixels.len(), None);
+        Ok(Frame::new(format!("signal strength {signal_strength}"), screen))
+    }
+}
+
+impl Animate for Day10 {
+    /// The screen after every cycle, with the sprite in yellow and the
+    /// pixel just drawn in red.
+    fn animate(commands: &Self::Input, config: &Config, sink: &mut FrameSink) -> Result<(), Box<dyn Error>> {
+        validate(config)?;
+        let blank = vec![' '; config.screen_width * config.screen_height];
+        sink.push(|| Frame::new("cycle 0, x = 1", draw_screen(&blank, config, 0, Some(1))))?;
+        let frame = |cycle: isize, x: isize, crt: &[char]| {
+            let drawn = (cycle as usize).min(crt.len());
+            Frame::new(format!("cycle {cycle}, x = {x}"), draw_screen(crt, config, drawn, Some(x)))
+        };
+        let mut last = (0, 1);
+        let (_, crt) = execute(commands, config, |cycle, x, crt| {
+            last = (cycle, x);
+            sink.push(|| frame(cycle, x, crt))
+        })?;
+        sink.finish(|| frame(last.0, last.1, &crt.pixels))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{
+        Puzzle,
+        animate::Animate,
+        explain::Explain,
+        generate::{Generate, input_rng},
+        ocr,
+        visualize::Visualize,
+    };
+
+    use super::{Config, Day10};
+
+    const EXAMPLE: &str = "\
+addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop";
+
+    const EXAMPLE_CRT: &str = "\
+██  ██  ██  ██  ██  ██  ██  ██  ██  ██  \n\
+███   ███   ███   ███   ███   ███   ███ \n\
+████    ████    ████    ████    ████    \n\
+█████     █████     █████     █████     \n\
+██████      ██████      ██████      ████\n\
+███████       ███████       ███████     ";
+
+    const INPUT_CRT: &str = "\
+███   ██   ██  ████ █  █ █    █  █ ████ \n\
+█  █ █  █ █  █ █    █ █  █    █  █ █    \n\
+███  █  █ █    ███  ██   █    ████ ███  \n\
+█  █ ████ █    █    █ █  █    █  █ █    \n\
+█  █ █  █ █  █ █    █ █  █    █  █ █    \n\
+███  █  █  ██  ████ █  █ ████ █  █ █    ";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day10::solve(EXAMPLE)?;
+        assert_eq!(answers.part1, 13140);
+        assert_eq!(answers.part2.to_string(), EXAMPLE_CRT);
+        Ok(())
+    }
+
+    #[test]
+    fn visualize() -> Result<(), Box<dyn Error>> {
+        let frame = Day10::draw(EXAMPLE, &Default::default())?;
+        let screen = EXAMPLE_CRT.replace('█', "#").replace(' ', ".");
+        assert_eq!(frame.ascii(), format!("signal strength 13140\n{screen}"));
+        Ok(())
+    }
+
+    #[test]
+    fn animate() -> Result<(), Box<dyn Error>> {
+        let frames = Day10::record(EXAMPLE, &Default::default())?;
+        assert_eq!(frames.len(), 241);
+        // During the third cycle the first addx has finished, moving the sprite far to the right.
+        let third = frames[3].ascii();
+        let lines = third.split('\n').map(str::trim_end).take(2).collect::<Vec<_>>();
+        assert_eq!(lines, ["cycle 3, x = 16", "##.            ==="]);
+        let screen = EXAMPLE_CRT.replace('█', "#").replace(' ', ".");
+        assert_eq!(frames[240].ascii(), format!("cycle 240, x = 17\n{screen}"));
+        let error = Day10::record(EXAMPLE, &Config { screen_width: 0, ..Default::default() }).unwrap_err();
+        assert_eq!(error.to_string(), "the cycle step and the screen width must be at least 1");
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day10::explain_str(EXAMPLE, &Default::default())?;
+        assert_eq!(explanation.len(), 7);
+        assert_eq!(explanation[0], "during cycle 20, x is 21: a signal strength of 420, 420 so far");
+        assert_eq!(explanation[5], "during cycle 220, x is 18: a signal strength of 3960, 13140 so far");
+        assert_eq!(explanation[6], "the signal strengths sum to 13140");
+        let config = Config { first_cycle: 1, last_cycle: 3, cycle_step: 1, ..Default::default() };
+        let explanation = Day10::explain_str(EXAMPLE, &config)?;
+        assert_eq!(explanation[0], "during cycle 1, x is 1: a signal strength of 1, 1 so far");
+        assert_eq!(explanation[3], "the signal strengths sum to 51");
+        assert_eq!(Day10::solve_with(EXAMPLE, &config)?.part1, 51);
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day10::solve(&read_to_string(Day10::INPUT_PATH)?)?;
+        assert_eq!(answers.part1, 16060);
+        assert_eq!(answers.part2.to_string(), INPUT_CRT);
+        assert_eq!(ocr::decode(INPUT_CRT).as_deref(), Some("BACEKLHF"));
+        Ok(())
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let input = Day10::generate(&mut input_rng(1), 500, &Default::default());
+        Day10::solve(&input)?;
+        Ok(())
+    }
+}
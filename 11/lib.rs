@@ -0,0 +1,586 @@
+use std::{cmp::Reverse, error::Error, rc::Rc};
+
+use common::{
+    Puzzle,
+    animate::{Animate, FrameSink},
+    explain::Explain,
+    explore::{Explore, argument, unknown_command},
+    generate::{Generate, InputRng},
+    grid::Grid,
+    parse::{Cursor, Expected, ParseContext},
+    visualize::{Frame, Pixel, Rgb},
+};
+use num::integer::gcd;
+use rand::{Rng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone)]
+pub struct Monkey {
+    items: Vec<usize>,
+    /// The new worry level, or `None` if it overflows.
+    operation: Rc<dyn Fn(usize) -> Option<usize>>,
+    test: (usize, usize, usize),
+    count: usize,
+}
+
+impl Monkey {
+    /// Parses the notes on the monkey at `index` in the order they are given.
+    fn parse(text: &str, index: usize) -> Result<Monkey, Expected> {
+        let mut cursor = Cursor::new(text);
+        cursor.tag("Monkey ")?;
+        let start = cursor.clone();
+        if cursor.number::<usize>()? != index {
+            return Err(start.expected(format!("monkey {index}")));
+        }
+        cursor.tag(":")?;
+        cursor.tag("\n")?;
+        cursor.field("Starting items")?;
+        let items = cursor.separated(", ", |cursor| cursor.number())?;
+        cursor.tag("\n")?;
+        cursor.field("Operation")?;
+        cursor.tag("new = old ")?;
+        let is_product = cursor.one_of(&["+", "*"])? == "*";
+        cursor.tag(" ")?;
+        let parameter = if cursor.eat("old") { None } else { Some(cursor.number()?) };
+        let operation = Rc::new(move |worry: usize| match is_product {
+            true => worry.checked_mul(parameter.unwrap_or(worry)),
+            false => worry.checked_add(parameter.unwrap_or(worry)),
+        });
+        cursor.tag("\n")?;
+        cursor.field("Test")?;
+        cursor.tag("divisible by ")?;
+        let start = cursor.clone();
+        let divisor = Some(cursor.number()?).filter(|divisor| *divisor > 0).ok_or_else(|| start.expected("a divisor of at least 1"))?;
+        cursor.tag("\n")?;
+        cursor.field("If true")?;
+        cursor.tag("throw to monkey ")?;
+        let if_true = cursor.number()?;
+        cursor.tag("\n")?;
+        cursor.field("If false")?;
+        cursor.tag("throw to monkey ")?;
+        let if_false = cursor.number()?;
+        cursor.end()?;
+        let test = (divisor, if_true, if_false);
+
+        Ok(Self {
+            items,
+            operation,
+            test,
+            count: 0,
+        })
+    }
+}
+
+fn play_round<F>(monkeys: &mut [Monkey], cap_worry_level: F) -> Result<(), Box<dyn Error>>
+where
+    F: Fn(usize) -> usize,
+{
+    for current_monkey in 0..monkeys.len() {
+        for worry_level in monkeys[current_monkey].items.drain(..).collect::<Vec<_>>() {
+            let new_worry_level = (monkeys[current_monkey].operation)(worry_level).ok_or("the worry level overflows")?;
+            let new_worry_level = cap_worry_level(new_worry_level);
+            let target_monkey = if new_worry_level.is_multiple_of(monkeys[current_monkey].test.0) {
+                monkeys[current_monkey].test.1
+            } else {
+                monkeys[current_monkey].test.2
+            };
+            monkeys
+                .get_mut(target_monkey)
+                .ok_or_else(|| format!("monkey {current_monkey} throws to monkey {target_monkey}, which does not exist"))?
+                .items
+                .push(new_worry_level);
+            monkeys[current_monkey].count += 1;
+        }
+    }
+    Ok(())
+}
+
+fn play_game<F>(monkeys: &mut [Monkey], rounds: usize, cap_worry_level: F) -> Result<usize, Box<dyn Error>>
+where
+    F: Fn(usize) -> usize,
+{
+    if monkeys.len() < 2 {
+        return Err("there must be at least two monkeys".into());
+    }
+    for _ in 0..rounds {
+        play_round(monkeys, &cap_worry_level)?;
+    }
+
+    monkeys.sort_by_key(|Monkey { count, .. }| Reverse(*count));
+
+    monkey_business(monkeys[0].count, monkeys[1].count)
+}
+
+/// The product of the two largest inspection counts, which many rounds can
+/// make too large to hold.
+fn monkey_business(first: usize, second: usize) -> Result<usize, Box<dyn Error>> {
+    Ok(first.checked_mul(second).ok_or("the monkey business overflows")?)
+}
+
+/// The least common multiple of the divisors, which worry levels can be
+/// taken modulo without changing where any item is thrown.
+fn common_multiple(monkeys: &[Monkey]) -> Result<usize, Box<dyn Error>> {
+    Ok(monkeys
+        .iter()
+        .try_fold(1usize, |modulus, monkey| (modulus / gcd(modulus, monkey.test.0)).checked_mul(monkey.test.0))
+        .ok_or("the divisors have too large a common multiple")?)
+}
+
+/// How many items every monkey inspects over the rounds, followed by the
+/// monkey business of the two busiest monkeys.
+fn explain_game<F>(monkeys: &[Monkey], rounds: usize, cap_worry_level: F, lines: &mut Vec<String>) -> Result<(), Box<dyn Error>>
+where
+    F: Fn(usize) -> usize,
+{
+    let mut monkeys = monkeys.to_vec();
+    for _ in 0..rounds {
+        play_round(&mut monkeys, &cap_worry_level)?;
+    }
+    lines.extend(
+        monkeys
+            .iter()
+            .enumerate()
+            .map(|(index, monkey)| format!("after {rounds} rounds, monkey {index} inspected {} items", monkey.count)),
+    );
+    let mut counts = monkeys.iter().map(|monkey| monkey.count).collect::<Vec<_>>();
+    counts.sort_by_key(|count| Reverse(*count));
+    if let [first, second, ..] = counts[..] {
+        lines.push(format!("the two busiest monkeys make {first} * {second} = {} monkey business", monkey_business(first, second)?));
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub rounds_part1: usize,
+    pub rounds_part2: usize,
+    /// What worry levels are divided by after each inspection in part 1.
+    pub relief: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            rounds_part1: 20,
+            rounds_part2: 10_000,
+            relief: 3,
+        }
+    }
+}
+
+pub struct Day11;
+
+impl Puzzle for Day11 {
+    const DAY: &'static str = "11";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = Vec<Monkey>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Config = Config;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        let mut index = 0;
+        Ok(context.blocks(input, 0, |text| {
+            index += 1;
+            Monkey::parse(text, index - 1)
+        })?)
+    }
+
+    fn part1(monkeys: &Self::Input, config: &Config) -> Result<usize, Box<dyn Error>> {
+        if config.relief == 0 {
+            return Err("the relief must be at least 1".into());
+        }
+        play_game(&mut monkeys.clone(), config.rounds_part1, |worry_level| {
+            worry_level / config.relief
+        })
+    }
+
+    fn part2(monkeys: &Self::Input, config: &Config) -> Result<usize, Box<dyn Error>> {
+        let modulus = common_multiple(monkeys)?;
+        play_game(&mut monkeys.clone(), config.rounds_part2, |worry_level| {
+            worry_level % modulus
+        })
+    }
+}
+
+impl Explain for Day11 {
+    /// The items every monkey inspects with relief, and then with worry
+    /// levels kept modulo the common multiple of the divisors.
+    fn explain(monkeys: &Self::Input, config: &Config) -> Result<Vec<String>, Box<dyn Error>> {
+        if config.relief == 0 {
+            return Err("the relief must be at least 1".into());
+        }
+        let mut lines = Vec::new();
+        explain_game(monkeys, config.rounds_part1, |worry_level| worry_level / config.relief, &mut lines)?;
+        let modulus = common_multiple(monkeys)?;
+        lines.push(format!("without relief, worry levels are kept modulo {modulus}"));
+        explain_game(monkeys, config.rounds_part2, |worry_level| worry_level % modulus, &mut lines)?;
+        Ok(lines)
+    }
+}
+
+fn describe_items(items: &[usize]) -> String {
+    match items.is_empty() {
+        true => "nothing".to_owned(),
+        false => items.iter().map(usize::to_string).collect::<Vec<_>>().join(", "),
+    }
+}
+
+impl Explore for Day11 {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        ("round N", "the items every monkey holds after N rounds of part 1, and how many it has inspected"),
+        ("monkey I", "the starting items of monkey I and where it throws them"),
+    ];
+
+    fn query(monkeys: &Self::Input, config: &Config, command: &str, arguments: &[&str]) -> Result<Vec<String>, Box<dyn Error>> {
+        match command {
+            "round" => {
+                let rounds = argument::<usize>(arguments, "a number of rounds")?;
+                if config.relief == 0 {
+                    return Err("the relief must be at least 1".into());
+                }
+                let mut monkeys = monkeys.clone();
+                for _ in 0..rounds {
+                    play_round(&mut monkeys, |worry_level| worry_level / config.relief)?;
+                }
+                Ok(monkeys
+                    .iter()
+                    .enumerate()
+                    .map(|(index, monkey)| {
+                        format!("monkey {index} holds {} and has inspected {}", describe_items(&monkey.items), monkey.count)
+                    })
+                    .collect())
+            }
+            "monkey" => {
+                let index = argument::<usize>(arguments, "a monkey")?;
+                let monkey = monkeys.get(index).ok_or_else(|| format!("no monkey {index}"))?;
+                let (divisor, if_true, if_false) = monkey.test;
+                Ok(vec![
+                    format!("starts with {}", describe_items(&monkey.items)),
+                    format!("throws to monkey {if_true} if divisible by {divisor}, to monkey {if_false} otherwise"),
+                ])
+            }
+            _ => Err(unknown_command(command)),
+        }
+    }
+}
+
+impl Generate for Day11 {
+    /// Notes on `size` monkeys, at least two. Worry levels are only ever
+    /// multiplied by constants and never squared, to keep part 1 from
+    /// overflowing on larger inputs.
+    fn generate(rng: &mut InputRng, size: usize, _config: &Self::Config) -> String {
+        const DIVISORS: [usize; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+        let count = size.max(2);
+        (0..count)
+            .map(|index| {
+                let items = (0..rng.gen_range(1..=6)).map(|_| rng.gen_range(50..=99).to_string()).collect::<Vec<_>>();
+                let operation = match rng.gen_bool(0.25) {
+                    true => format!("old * {}", rng.gen_range(2..=19)),
+                    false => format!("old + {}", rng.gen_range(1..=8)),
+                };
+                let mut target = || loop {
+                    let target = rng.gen_range(0..count);
+                    if target != index {
+                        break target;
+                    }
+                };
+                let (if_true, if_false) = (target(), target());
+                format!(
+                    "Monkey {index}:\n  Starting items: {}\n  Operation: new = {operation}\n  Test: divisible by {}\n    If true: throw to monkey {if_true}\n    If false: throw to monkey {if_false}",
+                    items.join(", "),
+                    DIVISORS.choose(rng).unwrap_or(&2),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// How many columns the longest inspection bar takes up.
+const BAR_WIDTH: usize = 40;
+
+/// One row per monkey: its number, the items it holds as `o` and how many
+/// items it has inspected as a bar, relative to the busiest monkey.
+fn draw_monkeys(monkeys: &[Monkey], item_count: usize) -> Grid<Pixel> {
+    let busiest = monkeys.iter().map(|monkey| monkey.count).max().unwrap_or_default().max(1);
+    Grid::new((0, 0), item_count + BAR_WIDTH + 3, monkeys.len(), ()).map(|(x, y), _| {
+        let monkey = &monkeys[y as usize];
+        match x as usize {
+            0 => Pixel::new(char::from_digit(y as u32 % 36, 36).unwrap_or('?'), Rgb::WHITE),
+            column if (2..2 + monkey.items.len()).contains(&column) => Pixel::new('o', Rgb::YELLOW),
+            column if column >= item_count + 3 && column - item_count - 3 < monkey.count * BAR_WIDTH / busiest => {
+                Pixel::new('=', Rgb::GREEN)
+            }
+            _ => Pixel::new(' ', Rgb::BLACK),
+        }
+    })
+}
+
+impl Animate for Day11 {
+    /// The monkeys after every round of part 1.
+    fn animate(monkeys: &Self::Input, config: &Config, sink: &mut FrameSink) -> Result<(), Box<dyn Error>> {
+        if config.relief == 0 {
+            return Err("the relief must be at least 1".into());
+        }
+        let item_count = monkeys.iter().map(|monkey| monkey.items.len()).sum();
+        let mut monkeys = monkeys.clone();
+        let mut caption = "the starting items".to_owned();
+        sink.push(|| Frame::new(caption.clone(), draw_monkeys(&monkeys, item_count)))?;
+        for round in 1..=config.rounds_part1 {
+            play_round(&mut monkeys, |worry_level| worry_level / config.relief)?;
+            let mut counts = monkeys.iter().map(|monkey| monkey.count).collect::<Vec<_>>();
+            counts.sort_by_key(|count| Reverse(*count));
+            caption = format!(
+                "round {round} of {}, monkey business {}",
+                config.rounds_part1,
+                counts.iter().take(2).product::<usize>()
+            );
+            sink.push(|| Frame::new(caption.clone(), draw_monkeys(&monkeys, item_count)))?;
+        }
+        sink.finish(|| Frame::new(caption, draw_monkeys(&monkeys, item_count)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{
+        Answers, Puzzle,
+        animate::Animate,
+        explain::Explain,
+        explore::Explore,
+        generate::{Generate, input_rng},
+        parse::{Mode, ParseContext},
+    };
+    use num::{BigUint, Zero};
+    use proptest::{collection::vec, prelude::*, sample::Index};
+
+    use super::{Config, Day11, monkey_business};
+
+    const EXAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day11::solve(EXAMPLE)?;
+        assert_eq!(answers, Answers { part1: 10605, part2: 2713310158 });
+        Ok(())
+    }
+
+    #[test]
+    fn animate() -> Result<(), Box<dyn Error>> {
+        let frames = Day11::record(EXAMPLE, &Default::default())?;
+        assert_eq!(frames.len(), 21);
+        let last = frames[20].ascii();
+        let rows = last.split('\n').map(str::trim_end).collect::<Vec<_>>();
+        assert_eq!(rows[0], "round 20 of 20, monkey business 10605");
+        assert_eq!(rows[1], format!("0 ooooo      {}", "=".repeat(101 * 40 / 105)));
+        assert_eq!(rows[3], format!("2            {}", "=".repeat(7 * 40 / 105)));
+        assert_eq!(rows[4], format!("3            {}", "=".repeat(40)));
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day11::explain_str(EXAMPLE, &Default::default())?;
+        assert_eq!(explanation[..5], [
+            "after 20 rounds, monkey 0 inspected 101 items",
+            "after 20 rounds, monkey 1 inspected 95 items",
+            "after 20 rounds, monkey 2 inspected 7 items",
+            "after 20 rounds, monkey 3 inspected 105 items",
+            "the two busiest monkeys make 105 * 101 = 10605 monkey business",
+        ]);
+        assert_eq!(explanation[5], "without relief, worry levels are kept modulo 96577");
+        assert_eq!(explanation[10], "the two busiest monkeys make 52166 * 52013 = 2713310158 monkey business");
+        Ok(())
+    }
+
+    #[test]
+    fn explore() -> Result<(), Box<dyn Error>> {
+        let monkeys = Day11::parse(EXAMPLE, &mut ParseContext::new(Day11::DAY, Mode::Strict))?;
+        let query = |command, arguments: &[&str]| Day11::query(&monkeys, &Default::default(), command, arguments);
+        assert_eq!(query("round", &["1"])?, [
+            "monkey 0 holds 20, 23, 27, 26 and has inspected 2",
+            "monkey 1 holds 2080, 25, 167, 207, 401, 1046 and has inspected 4",
+            "monkey 2 holds nothing and has inspected 3",
+            "monkey 3 holds nothing and has inspected 5",
+        ]);
+        assert_eq!(query("monkey", &["2"])?, [
+            "starts with 79, 60, 97",
+            "throws to monkey 1 if divisible by 13, to monkey 3 otherwise",
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day11::solve(&read_to_string(Day11::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: 62491, part2: 17408399184 });
+        Ok(())
+    }
+
+    #[test]
+    fn monkey_headers() {
+        let error = Day11::solve(&EXAMPLE.replace("Monkey 2:", "Monkey 3:")).unwrap_err();
+        assert_eq!(error.to_string(), "day 11, line 15, column 8: expected monkey 2");
+        let error = Day11::solve(&EXAMPLE.replacen("Monkey 0:", "Monkey 0", 1)).unwrap_err();
+        assert_eq!(error.to_string(), "day 11, line 1, column 9: expected \":\"");
+    }
+
+    #[test]
+    fn single_monkey() {
+        let monkey = "Monkey 0:\n  Starting items: 1\n  Operation: new = old + 1\n  Test: divisible by 2\n    If true: throw to monkey 0\n    If false: throw to monkey 0";
+        assert_eq!(Day11::solve(monkey).unwrap_err().to_string(), "there must be at least two monkeys");
+    }
+
+    #[test]
+    fn busy_monkeys() {
+        assert_eq!(monkey_business(101, 105).unwrap(), 10605);
+        assert_eq!(monkey_business(usize::MAX, 2).unwrap_err().to_string(), "the monkey business overflows");
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let input = Day11::generate(&mut input_rng(1), 8, &Default::default());
+        Day11::solve(&input)?;
+        Ok(())
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    enum Operation {
+        Add(u32),
+        Multiply(u32),
+        Square,
+    }
+
+    #[derive(Debug)]
+    struct Notes {
+        items: Vec<u32>,
+        operation: Operation,
+        divisor: u32,
+        targets: (usize, usize),
+    }
+
+    impl Notes {
+        fn write(&self, index: usize) -> String {
+            let operation = match self.operation {
+                Operation::Add(term) => format!("old + {term}"),
+                Operation::Multiply(factor) => format!("old * {factor}"),
+                Operation::Square => "old * old".to_owned(),
+            };
+            format!(
+                "Monkey {index}:\n  Starting items: {}\n  Operation: new = {operation}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}",
+                self.items.iter().map(u32::to_string).collect::<Vec<_>>().join(", "),
+                self.divisor,
+                self.targets.0,
+                self.targets.1,
+            )
+        }
+    }
+
+    /// Notes on two to five monkeys that never throw to themselves. Like
+    /// the real notes, at most one monkey squares its worry levels, which
+    /// keeps the exact worry levels of the reference small enough.
+    fn monkey_notes() -> impl Strategy<Value = Vec<Notes>> {
+        let operation = prop_oneof![
+            (1..10u32).prop_map(Operation::Add),
+            (2..20u32).prop_map(Operation::Multiply),
+            Just(Operation::Square),
+        ];
+        let monkey = (
+            vec(1..100u32, 1..5),
+            operation,
+            prop::sample::select(vec![2, 3, 5, 7, 11, 13, 17, 19, 23]),
+            any::<(Index, Index)>(),
+        );
+        vec(monkey, 2..=5).prop_map(|monkeys| {
+            let count = monkeys.len();
+            let mut squared = false;
+            monkeys
+                .into_iter()
+                .enumerate()
+                .map(|(index, (items, operation, divisor, (if_true, if_false)))| {
+                    let other = |choice: Index| (index + 1 + choice.index(count - 1)) % count;
+                    let operation = match operation {
+                        Operation::Square if squared => Operation::Multiply(2),
+                        operation => operation,
+                    };
+                    squared |= matches!(operation, Operation::Square);
+                    Notes {
+                        items,
+                        operation,
+                        divisor,
+                        targets: (other(if_true), other(if_false)),
+                    }
+                })
+                .collect()
+        })
+    }
+
+    /// Plays the game on the exact worry levels, without keeping them small.
+    fn exact_monkey_business(notes: &[Notes], rounds: usize) -> usize {
+        let mut items = notes
+            .iter()
+            .map(|monkey| monkey.items.iter().map(|item| BigUint::from(*item)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut inspections = vec![0; notes.len()];
+        for _ in 0..rounds {
+            for (index, monkey) in notes.iter().enumerate() {
+                for worry in std::mem::take(&mut items[index]) {
+                    let worry = match monkey.operation {
+                        Operation::Add(term) => worry + term,
+                        Operation::Multiply(factor) => worry * factor,
+                        Operation::Square => &worry * &worry,
+                    };
+                    let target = match (&worry % monkey.divisor).is_zero() {
+                        true => monkey.targets.0,
+                        false => monkey.targets.1,
+                    };
+                    items[target].push(worry);
+                    inspections[index] += 1;
+                }
+            }
+        }
+        inspections.sort_unstable_by(|a, b| b.cmp(a));
+        inspections[0] * inspections[1]
+    }
+
+    proptest! {
+        #[test]
+        fn lcm_modulus_matches_exact_worry_levels(notes in monkey_notes(), rounds in 1..=8usize) {
+            let input = notes.iter().enumerate().map(|(index, monkey)| monkey.write(index)).collect::<Vec<_>>();
+            let config = Config { rounds_part1: 0, rounds_part2: rounds, relief: 3 };
+            let answers = Day11::solve_with(&input.join("\n\n"), &config).unwrap();
+            prop_assert_eq!(answers.part2, exact_monkey_business(&notes, rounds));
+        }
+    }
+}
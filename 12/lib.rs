@@ -0,0 +1,226 @@
+use std::error::Error;
+
+use common::{
+    Puzzle,
+    config::NoConfig,
+    explain::Explain,
+    generate::{Generate, InputRng},
+    grid::{Grid, Point},
+    parse::{Expected, ParseContext},
+    visualize::{Frame, Pixel, Rgb, Visualize},
+};
+use pathfinding::directed::bfs::bfs;
+use rand::Rng;
+
+fn shortest_path(map: &Grid<i32>, start: &Point, end: &Point) -> Option<Vec<Point>> {
+    bfs(
+        start,
+        |&coord| {
+            let valid_elevs = 1..=map[coord] + 1;
+            map.neighbors4(coord)
+                .filter(move |(_, elev)| valid_elevs.contains(elev))
+                .map(|(neighbor, _)| neighbor)
+        },
+        |coord| coord == end,
+    )
+}
+
+fn find_path(map: &Grid<i32>, start: &Point, end: &Point) -> Option<usize> {
+    shortest_path(map, start, end).map(|path| path.len() - 1)
+}
+
+pub struct HeightMap {
+    map: Grid<i32>,
+    start: Point,
+    end: Point,
+}
+
+pub struct Day12;
+
+impl Puzzle for Day12 {
+    const DAY: &'static str = "12";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = HeightMap;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Config = NoConfig;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        let (mut start, mut end) = (None, None);
+        let map = Grid::parse(input, |coord, char| match char {
+            'S' => {
+                start = Some(coord);
+                Ok(1)
+            }
+            'E' => {
+                end = Some(coord);
+                Ok(26)
+            }
+            'a'..='z' => Ok(char as i32 - 'a' as i32 + 1),
+            _ => Err(Expected::line("an elevation from 'a' to 'z', 'S' or 'E'")),
+        })
+        .map_err(|expected| context.error(0, expected))?;
+
+        let (start, end) = (start.ok_or("no start")?, end.ok_or("no end")?);
+        Ok(HeightMap { map, start, end })
+    }
+
+    fn part1(HeightMap { map, start, end }: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        let len_part1 = find_path(map, start, end).ok_or("no path part 1")?;
+        Ok(len_part1)
+    }
+
+    fn part2(HeightMap { map, end, .. }: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        let len_part2 = map
+            .iter()
+            .filter(|(_, elev)| **elev == 1)
+            .filter_map(|(coord, _)| find_path(map, &coord, end))
+            .min()
+            .ok_or("no path part 2")?;
+        Ok(len_part2)
+    }
+}
+
+impl Explain for Day12 {
+    /// The path from the start, and which of the lowest squares has the
+    /// shortest path to the summit.
+    fn explain(HeightMap { map, start, end }: &Self::Input, _config: &Self::Config) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut lines = vec![format!("the start is at {start:?} and the summit at {end:?}")];
+        lines.push(match find_path(map, start, end) {
+            Some(length) => format!("the shortest path from the start takes {length} steps"),
+            None => "no path leads from the start to the summit".to_owned(),
+        });
+        let lowest = map.iter().filter(|(_, elev)| **elev == 1).map(|(coord, _)| coord).collect::<Vec<_>>();
+        let paths = lowest
+            .iter()
+            .filter_map(|coord| Some((*coord, find_path(map, coord, end)?)))
+            .collect::<Vec<_>>();
+        lines.push(format!("{} of the {} lowest squares lead to the summit", paths.len(), lowest.len()));
+        if let Some((coord, length)) = paths.iter().min_by_key(|(_, length)| *length) {
+            lines.push(format!("the shortest of them starts at {coord:?} and takes {length} steps"));
+        }
+        Ok(lines)
+    }
+}
+
+impl Generate for Day12 {
+    /// A heightmap `size` rows high and twice as wide, rising from the start
+    /// in the top left corner to the summit in the bottom right one. Pits
+    /// back down to 'a' are dug everywhere except along the top row and the
+    /// right column, which stay a climbable path.
+    fn generate(rng: &mut InputRng, size: usize, _config: &Self::Config) -> String {
+        let height = size.max(10);
+        let width = 2 * height;
+        let step = (width + height - 3) / 25;
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| match (x, y) {
+                        (0, 0) => 'S',
+                        _ if (x, y) == (width - 1, height - 1) => 'E',
+                        _ if y > 0 && x < width - 1 && rng.gen_bool(0.1) => 'a',
+                        _ => char::from(b'a' + ((x + y) / step).min(25) as u8),
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Visualize for Day12 {
+    /// The heightmap shaded from blue valleys to white peaks, with the
+    /// shortest path from the start drawn over it as arrows.
+    fn visualize(HeightMap { map, start, end }: &Self::Input, _config: &Self::Config) -> Result<Frame, Box<dyn Error>> {
+        let mut terrain = map.map(|_, elev| {
+            let glyph = char::from(b'a' + (*elev - 1) as u8);
+            Pixel::new(glyph, Rgb::BLUE.mix(Rgb::WHITE, (*elev - 1) as f64 / 25.0))
+        });
+        let path = shortest_path(map, start, end).unwrap_or_default();
+        for step in path.windows(2) {
+            let glyph = match (step[1].0 - step[0].0, step[1].1 - step[0].1) {
+                (1, _) => '>',
+                (-1, _) => '<',
+                (_, 1) => 'v',
+                _ => '^',
+            };
+            terrain[step[0]] = Pixel::new(glyph, Rgb::RED);
+        }
+        terrain[*start] = Pixel::new('S', Rgb::YELLOW);
+        terrain[*end] = Pixel::new('E', Rgb::YELLOW);
+        let caption = match path.len() {
+            0 => "no path from the start to the summit".to_owned(),
+            length => format!("the shortest path takes {} steps", length - 1),
+        };
+        Ok(Frame::new(caption, terrain))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{
+        Answers, Puzzle,
+        explain::Explain,
+        generate::{Generate, input_rng},
+        visualize::Visualize,
+    };
+
+    use super::Day12;
+
+    const EXAMPLE: &str = "\
+Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day12::solve(EXAMPLE)?;
+        assert_eq!(answers, Answers { part1: 31, part2: 29 });
+        Ok(())
+    }
+
+    #[test]
+    fn visualize() -> Result<(), Box<dyn Error>> {
+        let frame = Day12::draw(EXAMPLE, &Default::default())?;
+        let path = "\
+the shortest path takes 31 steps
+S>vv<<<<
+abvvv<<^
+acvv>E^^
+acv>>>^^
+ab>>>>>^";
+        assert_eq!(frame.ascii(), path);
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day12::explain_str(EXAMPLE, &Default::default())?;
+        assert_eq!(explanation, [
+            "the start is at (0, 0) and the summit at (5, 2)",
+            "the shortest path from the start takes 31 steps",
+            "6 of the 6 lowest squares lead to the summit",
+            "the shortest of them starts at (0, 4) and takes 29 steps",
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day12::solve(&read_to_string(Day12::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: 497, part2: 492 });
+        Ok(())
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let input = Day12::generate(&mut input_rng(1), 15, &Default::default());
+        Day12::solve(&input)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,424 @@
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    slice,
+};
+
+use common::{
+    Puzzle,
+    config::NoConfig,
+    explain::Explain,
+    explore::{Explore, argument, unknown_command},
+    generate::{Generate, InputRng},
+    parse::{Cursor, Expected, ParseContext},
+};
+use rand::Rng;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Packet {
+    List(Vec<Packet>),
+    Number(u8),
+}
+
+/// How deeply lists may nest, so that parsing, comparing and dropping
+/// packets cannot overflow the stack.
+const MAX_DEPTH: usize = 100;
+
+impl Packet {
+    fn parse(cursor: &mut Cursor, depth: usize) -> Result<Packet, Expected> {
+        if !cursor.eat("[") {
+            if !cursor.rest().starts_with(|char: char| char.is_ascii_digit()) {
+                return Err(cursor.expected("a number or a list"));
+            }
+            return Ok(Self::Number(cursor.number()?));
+        }
+        if depth == MAX_DEPTH {
+            return Err(cursor.expected(format!("lists nested at most {MAX_DEPTH} deep")));
+        }
+        if cursor.eat("]") {
+            return Ok(Self::List(Vec::new()));
+        }
+        let packets = cursor.separated(",", |cursor| Self::parse(cursor, depth + 1))?;
+        cursor.tag("]").map_err(|_| cursor.expected("\",\" or \"]\""))?;
+        Ok(Self::List(packets))
+    }
+}
+
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{number}"),
+            Self::List(packets) => write_list(f, packets),
+        }
+    }
+}
+
+fn write_list(f: &mut Formatter<'_>, packets: &[Packet]) -> fmt::Result {
+    write!(f, "[")?;
+    for (index, packet) in packets.iter().enumerate() {
+        if index > 0 {
+            write!(f, ",")?;
+        }
+        write!(f, "{packet}")?;
+    }
+    write!(f, "]")
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match compare((self, other)) {
+            None => Ordering::Equal,
+            Some(true) => Ordering::Less,
+            Some(false) => Ordering::Greater,
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The comparison that decides the order of a pair.
+enum Reason<'p> {
+    /// Two different numbers.
+    Numbers(u8, u8),
+    /// A list that runs out before the other one.
+    RunsOut(&'p [Packet]),
+}
+
+impl Display for Reason<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Numbers(left, right) => write!(f, "{left} is {} than {right}", if left < right { "smaller" } else { "larger" }),
+            Self::RunsOut(packets) => {
+                write_list(f, packets)?;
+                write!(f, " runs out first")
+            }
+        }
+    }
+}
+
+/// Whether a pair is in the right order, or `None` if the packets are equal.
+fn compare(packet_pair: (&Packet, &Packet)) -> Option<bool> {
+    decide(packet_pair).map(|(ordered, _)| ordered)
+}
+
+/// Whether a pair is in the right order, with the comparison that decides
+/// it. A number compared with a list is a list holding just that number.
+fn decide<'p>(packet_pair: (&'p Packet, &'p Packet)) -> Option<(bool, Reason<'p>)> {
+    let as_list = |packet: &'p Packet| match packet {
+        Packet::Number(_) => slice::from_ref(packet),
+        Packet::List(packets) => packets.as_slice(),
+    };
+    match packet_pair {
+        (Packet::Number(left), Packet::Number(right)) => (left != right).then_some((left < right, Reason::Numbers(*left, *right))),
+        (left, right) => {
+            let (left_list, right_list) = (as_list(left), as_list(right));
+            left_list.iter().zip(right_list).find_map(decide).or(match left_list.len().cmp(&right_list.len()) {
+                Ordering::Equal => None,
+                Ordering::Less => Some((true, Reason::RunsOut(left_list))),
+                Ordering::Greater => Some((false, Reason::RunsOut(right_list))),
+            })
+        }
+    }
+}
+
+/// Whether a pair is in the right order, and why.
+fn judge(left: &Packet, right: &Packet) -> String {
+    match decide((left, right)) {
+        Some((true, reason)) => format!("is in the right order: {reason}"),
+        Some((false, reason)) => format!("is in the wrong order: {reason}"),
+        None => "holds two equal packets".to_owned(),
+    }
+}
+
+pub struct Day13;
+
+impl Puzzle for Day13 {
+    const DAY: &'static str = "13";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = Vec<(Packet, Packet)>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Config = NoConfig;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(context.blocks(input, 0, |packet_pair_text| {
+            let mut cursor = Cursor::new(packet_pair_text);
+            let packet_1 = Packet::parse(&mut cursor, 0)?;
+            cursor.tag("\n")?;
+            let packet_2 = Packet::parse(&mut cursor, 0)?;
+            cursor.end()?;
+            Ok((packet_1, packet_2))
+        })?)
+    }
+
+    fn part1(packet_pairs: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        let correct_index_sum = packet_pairs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (left, right))| {
+                if compare((left, right))? {
+                    Some(index + 1)
+                } else {
+                    None
+                }
+            })
+            .sum::<usize>();
+        Ok(correct_index_sum)
+    }
+
+    fn part2(packet_pairs: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        let divider_two = Packet::List(vec![Packet::List(vec![Packet::Number(2)])]);
+        let divider_six = Packet::List(vec![Packet::List(vec![Packet::Number(6)])]);
+        let mut all_packets = packet_pairs
+            .iter()
+            .flat_map(|(left, right)| [left.clone(), right.clone()])
+            .chain([divider_two.clone(), divider_six.clone()])
+            .collect::<Vec<_>>();
+
+        all_packets.sort();
+        let (divider_two_loc, divider_six_loc) = all_packets.into_iter().enumerate().fold(
+            (None, None),
+            |(divider_two_loc, divider_six_loc), (index, packet)| {
+                (
+                    divider_two_loc.or(if packet == divider_two {
+                        Some(index + 1)
+                    } else {
+                        None
+                    }),
+                    divider_six_loc.or(if packet == divider_six {
+                        Some(index + 1)
+                    } else {
+                        None
+                    }),
+                )
+            },
+        );
+
+        let decoder_key =
+            divider_two_loc.ok_or("no divider two")? * divider_six_loc.ok_or("no divider six")?;
+        Ok(decoder_key)
+    }
+}
+
+impl Explain for Day13 {
+    /// Whether every pair is in the right order and the comparison that
+    /// decides it, then where the divider packets end up.
+    fn explain(packet_pairs: &Self::Input, _config: &Self::Config) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut lines = packet_pairs
+            .iter()
+            .enumerate()
+            .map(|(index, (left, right))| format!("pair {} {}", index + 1, judge(left, right)))
+            .collect::<Vec<_>>();
+        // Every packet sorted before a divider moves it one place down, as
+        // does the first divider for the second.
+        let packets = packet_pairs.iter().flat_map(|(left, right)| [left, right]).collect::<Vec<_>>();
+        for (divider, offset) in [(2, 1), (6, 2)] {
+            let divider_packet = Packet::List(vec![Packet::List(vec![Packet::Number(divider)])]);
+            let before = packets.iter().filter(|packet| ***packet < divider_packet).count();
+            lines.push(format!("{before} packets sort before {divider_packet}, which ends up at {}", before + offset));
+        }
+        Ok(lines)
+    }
+}
+
+impl Explore for Day13 {
+    const COMMANDS: &'static [(&'static str, &'static str)] =
+        &[("compare N", "the packets of pair N, counted from 1, and which comparison orders them")];
+
+    fn query(packet_pairs: &Self::Input, _config: &Self::Config, command: &str, arguments: &[&str]) -> Result<Vec<String>, Box<dyn Error>> {
+        match command {
+            "compare" => {
+                let number = argument::<usize>(arguments, "a pair number")?;
+                let (left, right) = number
+                    .checked_sub(1)
+                    .and_then(|index| packet_pairs.get(index))
+                    .ok_or_else(|| format!("no pair {number}, there are {}", packet_pairs.len()))?;
+                Ok(vec![left.to_string(), right.to_string(), format!("pair {number} {}", judge(left, right))])
+            }
+            _ => Err(unknown_command(command)),
+        }
+    }
+}
+
+impl Generate for Day13 {
+    /// `size` pairs of packets nested up to four lists deep.
+    fn generate(rng: &mut InputRng, size: usize, _config: &Self::Config) -> String {
+        (0..size)
+            .map(|_| format!("{}\n{}", random_list(rng, 4), random_list(rng, 4)))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+fn random_list(rng: &mut InputRng, depth: usize) -> String {
+    let packets = (0..rng.gen_range(0..=4))
+        .map(|_| match depth > 0 && rng.gen_bool(0.3) {
+            true => random_list(rng, depth - 1),
+            false => rng.gen_range(0..=10).to_string(),
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", packets.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cmp::Ordering, error::Error, fs::read_to_string};
+
+    use common::{
+        Answers, Puzzle,
+        explain::Explain,
+        explore::Explore,
+        generate::{Generate, input_rng},
+        parse::{Mode, ParseContext},
+    };
+    use proptest::{collection::vec, prelude::*};
+
+    use super::Day13;
+
+    const EXAMPLE: &str = "\
+[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day13::solve(EXAMPLE)?;
+        assert_eq!(answers, Answers { part1: 13, part2: 140 });
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day13::explain_str(EXAMPLE, &Default::default())?;
+        assert_eq!(explanation, [
+            "pair 1 is in the right order: 3 is smaller than 5",
+            "pair 2 is in the right order: 2 is smaller than 4",
+            "pair 3 is in the wrong order: 9 is larger than 8",
+            "pair 4 is in the right order: [[4,4],4,4] runs out first",
+            "pair 5 is in the wrong order: [7,7,7] runs out first",
+            "pair 6 is in the right order: [] runs out first",
+            "pair 7 is in the wrong order: [] runs out first",
+            "pair 8 is in the wrong order: 7 is larger than 0",
+            "9 packets sort before [[2]], which ends up at 10",
+            "12 packets sort before [[6]], which ends up at 14",
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn explore() -> Result<(), Box<dyn Error>> {
+        let packet_pairs = Day13::parse(EXAMPLE, &mut ParseContext::new(Day13::DAY, Mode::Strict))?;
+        let query = |arguments: &[&str]| Day13::query(&packet_pairs, &Default::default(), "compare", arguments);
+        assert_eq!(query(&["3"])?, ["[9]", "[[8,7,6]]", "pair 3 is in the wrong order: 9 is larger than 8"]);
+        assert_eq!(query(&["9"]).unwrap_err().to_string(), "no pair 9, there are 8");
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day13::solve(&read_to_string(Day13::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: 6070, part2: 20758 });
+        Ok(())
+    }
+
+    #[test]
+    fn deeply_nested_packets() {
+        let packet = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        let error = Day13::solve(&format!("{packet}\n[]")).unwrap_err();
+        assert_eq!(error.to_string(), "day 13, line 1, column 102: expected lists nested at most 100 deep");
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let input = Day13::generate(&mut input_rng(1), 500, &Default::default());
+        Day13::solve(&input)?;
+        Ok(())
+    }
+
+    #[derive(Clone, Debug)]
+    enum Value {
+        Number(u8),
+        List(Vec<Value>),
+    }
+
+    impl Value {
+        fn write(&self) -> String {
+            match self {
+                Value::Number(number) => number.to_string(),
+                Value::List(values) => format!("[{}]", values.iter().map(Value::write).collect::<Vec<_>>().join(",")),
+            }
+        }
+    }
+
+    /// Compares two values by following the rules of the puzzle word by word.
+    fn order(left: &Value, right: &Value) -> Ordering {
+        match (left, right) {
+            (Value::Number(left), Value::Number(right)) => left.cmp(right),
+            (Value::List(left), Value::List(right)) => {
+                for (left, right) in left.iter().zip(right) {
+                    let ordering = order(left, right);
+                    if ordering != Ordering::Equal {
+                        return ordering;
+                    }
+                }
+                left.len().cmp(&right.len())
+            }
+            (Value::Number(left), right) => order(&Value::List(vec![Value::Number(*left)]), right),
+            (left, Value::Number(right)) => order(left, &Value::List(vec![Value::Number(*right)])),
+        }
+    }
+
+    fn packet() -> impl Strategy<Value = Value> {
+        let value = (0..=10u8).prop_map(Value::Number).prop_recursive(4, 32, 4, |inner| {
+            vec(inner, 0..=4).prop_map(Value::List)
+        });
+        vec(value, 0..=4).prop_map(Value::List)
+    }
+
+    proptest! {
+        #[test]
+        fn compare_matches_reference_order(pairs in vec((packet(), packet()), 1..10)) {
+            let dividers = [2, 6].map(|number| Value::List(vec![Value::List(vec![Value::Number(number)])]));
+            let packets = pairs.iter().flat_map(|(left, right)| [left, right]).collect::<Vec<_>>();
+            // The real packets never tie with a divider, which would leave its position open.
+            prop_assume!(packets.iter().all(|packet| dividers.iter().all(|divider| order(packet, divider) != Ordering::Equal)));
+
+            let input = pairs.iter().map(|(left, right)| format!("{}\n{}", left.write(), right.write())).collect::<Vec<_>>();
+            let answers = Day13::solve(&input.join("\n\n")).unwrap();
+            let ordered_index_sum = (1..=pairs.len())
+                .filter(|index| order(&pairs[index - 1].0, &pairs[index - 1].1) == Ordering::Less)
+                .sum::<usize>();
+            // A divider ends up after every packet ordered before it, and the second one also after the first.
+            let position = |divider| 1 + packets.iter().filter(|packet| order(packet, divider) == Ordering::Less).count();
+            let decoder_key = position(&dividers[0]) * (position(&dividers[1]) + 1);
+            prop_assert_eq!(answers.part1, ordered_index_sum);
+            prop_assert_eq!(answers.part2, decoder_key);
+        }
+    }
+}
@@ -0,0 +1,359 @@
+use std::error::Error;
+
+use common::{
+    Puzzle,
+    animate::{Animate, FrameSink},
+    explain::Explain,
+    generate::{Generate, InputRng},
+    grid::{Grid, Point, PointSet},
+    parse::{Cursor, Expected, ParseContext},
+    visualize::{Frame, Pixel, Rgb, Visualize},
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// The rocks of the cave, the corners of the smallest rectangle around
+/// them, and the depth of the lowest one.
+pub struct Cave {
+    rocks: PointSet,
+    corners: (Point, Point),
+    bottom: isize,
+}
+
+impl Cave {
+    /// A copy of the rocks with enough room around them for all the sand
+    /// falling from `source`.
+    fn with_room_for_sand(&self, source: Point) -> PointSet {
+        // Sand piling up on the floor spreads at most one column per row to either side.
+        let spread = (self.bottom - source.1).max(0) + 3;
+        let (top_left, bottom_right) = self.corners;
+        let mut cave = PointSet::spanning(
+            (top_left.0.min(source.0 - spread), top_left.1.min(source.1)),
+            (bottom_right.0.max(source.0 + spread), bottom_right.1.max(source.1).max(self.bottom + 2)),
+        );
+        for rock in self.rocks.iter() {
+            cave.insert(rock);
+        }
+        cave
+    }
+}
+
+/// Where the next unit of sand comes to rest, if it does, given which
+/// positions are already filled.
+fn drop_sand(filled: impl Fn(Point) -> bool, source: Point, bottom: isize, floor: bool) -> Option<Point> {
+    let mut sand = source;
+    let free = |coord| !filled(coord);
+    loop {
+        if sand.1 + 1 > bottom && !floor {
+            break None;
+        } else if sand.1 > bottom {
+            break Some(sand);
+        } else if free((sand.0, sand.1 + 1)) {
+            sand.1 += 1;
+        } else if free((sand.0 - 1, sand.1 + 1)) {
+            sand = (sand.0 - 1, sand.1 + 1);
+        } else if free((sand.0 + 1, sand.1 + 1)) {
+            sand = (sand.0 + 1, sand.1 + 1);
+        } else {
+            break Some(sand);
+        }
+    }
+}
+
+/// Pours sand until it falls out of the cave or piles up to the source,
+/// returning the rocks and sand, and how much sand came to rest.
+fn pour_sand(cave: &Cave, source: Point, floor: bool) -> (PointSet, usize) {
+    let mut rocks = cave.with_room_for_sand(source);
+    let mut drop_counter = 0;
+    while let Some(sand) = drop_sand(|coord| rocks.contains(coord), source, cave.bottom, floor) {
+        drop_counter += 1;
+        rocks.insert(sand);
+        // Rocks that form a basin around the source fill up before any sand falls out.
+        if sand == source {
+            break;
+        }
+    }
+    (rocks, drop_counter)
+}
+
+/// The grid of a cave, which drawing it needs.
+fn cave_grid(cave: &PointSet) -> Result<&Grid<bool>, Box<dyn Error>> {
+    Ok(cave.grid().ok_or("the cave is too large to draw")?)
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Where the sand pours into the cave from.
+    pub sand_source: Point,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { sand_source: (500, 0) }
+    }
+}
+
+pub struct Day14;
+
+impl Puzzle for Day14 {
+    const DAY: &'static str = "14";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = Cave;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Config = Config;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        let paths = context.lines(input, 0, |line| {
+            let mut cursor = Cursor::new(line);
+            let coord = |cursor: &mut Cursor| {
+                let x = cursor.signed::<i16>()?;
+                cursor.tag(",")?;
+                Ok::<_, Expected>((x, cursor.signed::<i16>()?))
+            };
+            let mut path = vec![coord(&mut cursor)?];
+            while !cursor.is_empty() {
+                cursor.tag(" -> ")?;
+                path.push(coord(&mut cursor)?);
+            }
+            Ok(path)
+        })?;
+        let points = || paths.iter().flatten().map(|&(x, y)| (x as isize, y as isize));
+        let top_left = points()
+            .reduce(|(x1, y1), (x2, y2)| (x1.min(x2), y1.min(y2)))
+            .unwrap_or_default();
+        let bottom_right = points()
+            .reduce(|(x1, y1), (x2, y2)| (x1.max(x2), y1.max(y2)))
+            .unwrap_or_default();
+        let bottom = bottom_right.1;
+        let mut rocks = PointSet::spanning(top_left, bottom_right);
+        for path in &paths {
+            let (mut x1, mut y1) = (path[0].0 as isize, path[0].1 as isize);
+            rocks.insert((x1, y1));
+            for &(x2, y2) in &path[1..] {
+                let (x2, y2) = (x2 as isize, y2 as isize);
+                while x1 != x2 || y1 != y2 {
+                    x1 += (x2 - x1).signum();
+                    y1 += (y2 - y1).signum();
+                    rocks.insert((x1, y1));
+                }
+            }
+        }
+        Ok(Cave { rocks, corners: (top_left, bottom_right), bottom })
+    }
+
+    fn part1(cave: &Self::Input, config: &Config) -> Result<usize, Box<dyn Error>> {
+        let (_, drop_counter) = pour_sand(cave, config.sand_source, false);
+        Ok(drop_counter)
+    }
+
+    fn part2(cave: &Self::Input, config: &Config) -> Result<usize, Box<dyn Error>> {
+        let (_, drop_counter) = pour_sand(cave, config.sand_source, true);
+        Ok(drop_counter)
+    }
+}
+
+impl Explain for Day14 {
+    /// How deep the rocks reach, and how much sand comes to rest with and
+    /// without the floor below them.
+    fn explain(cave: &Self::Input, config: &Config) -> Result<Vec<String>, Box<dyn Error>> {
+        let rock_count = cave.rocks.len();
+        let mut lines = vec![
+            format!("{rock_count} rocks reach down to depth {}, so the floor is at {}", cave.bottom, cave.bottom + 2),
+        ];
+        let (_, resting) = pour_sand(cave, config.sand_source, false);
+        lines.push(format!("without a floor, {resting} units of sand come to rest before it falls past the rocks"));
+        let (with_floor, resting_on_floor) = pour_sand(cave, config.sand_source, true);
+        let sand_columns = with_floor.iter().filter(|coord| !cave.rocks.contains(*coord)).map(|(x, _)| x);
+        let (left, right) = sand_columns.fold((isize::MAX, isize::MIN), |(left, right), x| (left.min(x), right.max(x)));
+        lines.push(format!(
+            "with a floor, {resting_on_floor} units of sand come to rest from x = {left} to {right} before they bury the source"
+        ));
+        Ok(lines)
+    }
+}
+
+impl Generate for Day14 {
+    /// `size` paths of horizontal and vertical rock lines below the sand
+    /// source, spread wider the more there are so that they rarely form
+    /// basins deep enough to bury the source.
+    fn generate(rng: &mut InputRng, size: usize, config: &Self::Config) -> String {
+        let (source_x, source_y) = config.sand_source;
+        let spread = (2 * size as isize).max(60);
+        (0..size)
+            .map(|_| {
+                let mut point = (
+                    rng.gen_range(source_x - spread..=source_x + spread),
+                    rng.gen_range(source_y + 2..=source_y + 170),
+                );
+                let mut points = vec![point];
+                for index in 0..rng.gen_range(1..=5) {
+                    let length = rng.gen_range(-12..=12);
+                    point = match index % 2 {
+                        0 => (point.0 + length, point.1),
+                        _ => (point.0, (point.1 + length).max(source_y + 2)),
+                    };
+                    points.push(point);
+                }
+                points.iter().map(|(x, y)| format!("{x},{y}")).collect::<Vec<_>>().join(" -> ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The cave with rock as `#`, the sand in `first_pile` as `o` and any
+/// other sand as `~`.
+fn draw_cave(cave: &Cave, source: Point, filled: &Grid<bool>, first_pile: &Grid<bool>) -> Grid<Pixel> {
+    let is_rock = |coord| cave.rocks.contains(coord) || coord.1 == cave.bottom + 2;
+    filled.map(|coord, filled| {
+        if coord == source {
+            Pixel::new('+', Rgb::RED)
+        } else if is_rock(coord) {
+            Pixel::new('#', Rgb::GREY)
+        } else if first_pile[coord] {
+            Pixel::new('o', Rgb::YELLOW)
+        } else if *filled {
+            Pixel::new('~', Rgb::YELLOW.mix(Rgb::DARK_GREY, 0.5))
+        } else {
+            Pixel::new('.', Rgb::DARK_GREY)
+        }
+    })
+}
+
+impl Visualize for Day14 {
+    /// The cave once sand piles up to the source on the floor, where `o`
+    /// is the sand that comes to rest without a floor and `~` the sand
+    /// that only does with one.
+    fn visualize(cave: &Self::Input, config: &Config) -> Result<Frame, Box<dyn Error>> {
+        let (without_floor, resting) = pour_sand(cave, config.sand_source, false);
+        // Fails before piling up the sand on the floor, which takes longest.
+        let without_floor = cave_grid(&without_floor)?;
+        let (with_floor, resting_on_floor) = pour_sand(cave, config.sand_source, true);
+        let caption = format!("{resting} units of sand come to rest without a floor, {resting_on_floor} with one");
+        Ok(Frame::new(caption, draw_cave(cave, config.sand_source, cave_grid(&with_floor)?, without_floor)))
+    }
+}
+
+impl Animate for Day14 {
+    /// The cave after every unit of sand comes to rest, first without a
+    /// floor until sand falls into the abyss, and then with one. Sand
+    /// piles up the same way until then either way.
+    fn animate(cave: &Self::Input, config: &Config, sink: &mut FrameSink) -> Result<(), Box<dyn Error>> {
+        let source = config.sand_source;
+        let mut filled = cave_grid(&cave.with_room_for_sand(source))?.clone();
+        let mut caption = "no sand at rest".to_owned();
+        sink.push(|| Frame::new(caption.clone(), draw_cave(cave, source, &filled, &filled)))?;
+        let mut resting = 0;
+        let mut first_pile = None;
+        for floor in [false, true] {
+            while !filled[source] && let Some(sand) = drop_sand(|coord| filled[coord], source, cave.bottom, floor) {
+                filled[sand] = true;
+                resting += 1;
+                let floor = if floor { "with a floor" } else { "without a floor" };
+                caption = format!("{resting} units of sand at rest {floor}");
+                let first_pile = first_pile.as_ref().unwrap_or(&filled);
+                sink.push(|| Frame::new(caption.clone(), draw_cave(cave, source, &filled, first_pile)))?;
+            }
+            first_pile = Some(filled.clone());
+        }
+        let first_pile = first_pile.as_ref().unwrap_or(&filled);
+        sink.finish(|| Frame::new(caption, draw_cave(cave, source, &filled, first_pile)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{
+        Answers, Puzzle,
+        animate::Animate,
+        explain::Explain,
+        generate::{Generate, input_rng},
+        parse::{Mode, ParseContext},
+        visualize::Visualize,
+    };
+
+    use super::Day14;
+
+    const EXAMPLE: &str = "\
+498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day14::solve(EXAMPLE)?;
+        assert_eq!(answers, Answers { part1: 24, part2: 93 });
+        Ok(())
+    }
+
+    #[test]
+    fn wide_cave() -> Result<(), Box<dyn Error>> {
+        // Too wide and deep for a dense grid of the whole cave.
+        let cave = format!("{EXAMPLE}\n-32000,300\n32000,0");
+        let rocks = Day14::parse(&cave, &mut ParseContext::new(Day14::DAY, Mode::Strict))?;
+        assert_eq!(Day14::part1(&rocks, &Default::default())?, 24);
+        let error = Day14::draw(&cave, &Default::default()).unwrap_err();
+        assert_eq!(error.to_string(), "the cave is too large to draw");
+        Ok(())
+    }
+
+    #[test]
+    fn visualize() -> Result<(), Box<dyn Error>> {
+        let frame = Day14::draw(EXAMPLE, &Default::default())?;
+        let cave = "\
+24 units of sand come to rest without a floor, 93 with one
+............+............
+...........~~~...........
+..........~~o~~..........
+.........~~ooo~~.........
+........~~#ooo##~........
+.......~~o#ooo#~~~.......
+......~~###ooo#~~~~......
+.....~~~~.oooo#~~~~~.....
+....~~~o~ooooo#~~~~~~....
+...~~~#########~~~~~~~...
+..~~~~~.......~~~~~~~~~..
+#########################";
+        assert_eq!(frame.ascii(), cave);
+        Ok(())
+    }
+
+    #[test]
+    fn animate() -> Result<(), Box<dyn Error>> {
+        let frames = Day14::record(EXAMPLE, &Default::default())?;
+        assert_eq!(frames.len(), 94);
+        assert_eq!(frames[24].caption, "24 units of sand at rest without a floor");
+        assert_eq!(frames[25].caption, "25 units of sand at rest with a floor");
+        assert_eq!(frames[93].pixels, Day14::draw(EXAMPLE, &Default::default())?.pixels);
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day14::explain_str(EXAMPLE, &Default::default())?;
+        assert_eq!(explanation, [
+            "20 rocks reach down to depth 9, so the floor is at 11",
+            "without a floor, 24 units of sand come to rest before it falls past the rocks",
+            "with a floor, 93 units of sand come to rest from x = 490 to 510 before they bury the source",
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day14::solve(&read_to_string(Day14::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: 610, part2: 27194 });
+        Ok(())
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let input = Day14::generate(&mut input_rng(1), 100, &Default::default());
+        Day14::solve(&input)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,520 @@
+use std::{collections::HashSet, error::Error};
+
+use common::{
+    Puzzle,
+    explain::Explain,
+    explore::{Explore, argument, unknown_command},
+    generate::{Generate, InputRng},
+    grid::Grid,
+    parse::{Cursor, Expected, ParseContext},
+    visualize::{Frame, Pixel, Rgb, Visualize},
+};
+use rand::{Rng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+
+/// What the distress beacon's x coordinate is multiplied by in its tuning frequency.
+const TUNING_FACTOR: isize = 4_000_000;
+
+pub struct Sensor {
+    sensor_x: isize,
+    sensor_y: isize,
+    beacon_x: isize,
+    beacon_y: isize,
+}
+
+impl Sensor {
+    fn parse(text: &str) -> Result<Self, Expected> {
+        let mut cursor = Cursor::new(text);
+        // Coordinates fit in 32 bits, so that distances between them cannot overflow.
+        let mut coordinate = |tag| {
+            cursor.tag(tag)?;
+            Ok::<_, Expected>(cursor.signed::<i32>()? as isize)
+        };
+        let sensor_x = coordinate("Sensor at x=")?;
+        let sensor_y = coordinate(", y=")?;
+        let beacon_x = coordinate(": closest beacon is at x=")?;
+        let beacon_y = coordinate(", y=")?;
+        cursor.end()?;
+        Ok(Self {
+            sensor_x,
+            sensor_y,
+            beacon_x,
+            beacon_y,
+        })
+    }
+
+    fn distance(&self) -> isize {
+        (self.sensor_x - self.beacon_x).abs() + (self.sensor_y - self.beacon_y).abs()
+    }
+
+    fn blocked_range(&self, row: isize) -> Option<(isize, isize)> {
+        let remaining_distance = self.distance() - (self.sensor_y - row).abs();
+        if remaining_distance < 0 {
+            None
+        } else {
+            Some((
+                self.sensor_x - remaining_distance,
+                self.sensor_x + remaining_distance,
+            ))
+        }
+    }
+}
+
+fn compute_blocked_ranges(sensors: &[Sensor], row: isize) -> Vec<(isize, isize)> {
+    let mut raw_blocked_ranges = sensors
+        .iter()
+        .filter_map(|sensor| sensor.blocked_range(row))
+        .collect::<Vec<_>>();
+    raw_blocked_ranges.sort_by_key(|(start, _)| *start);
+
+    let mut blocked_ranges = Vec::new();
+    while !raw_blocked_ranges.is_empty() {
+        let mut r1 = raw_blocked_ranges.remove(0);
+        let mut index = 0;
+        while index < raw_blocked_ranges.len() {
+            let r2 = raw_blocked_ranges[index];
+            if r1.0 <= r2.0 && r1.1 >= r2.1 {
+                raw_blocked_ranges.remove(index);
+                continue;
+            } else if r1.0 >= r2.0 && r1.1 <= r2.1 {
+                r1 = r2;
+                raw_blocked_ranges.remove(index);
+                continue;
+            } else if r1.0 <= r2.0 && r1.1 <= r2.1 && r1.1 >= r2.0 {
+                r1.1 = r2.1;
+                raw_blocked_ranges.remove(index);
+                continue;
+            } else if r1.0 >= r2.0 && r1.1 >= r2.1 && r2.1 >= r1.0 {
+                r1.0 = r2.0;
+                raw_blocked_ranges.remove(index);
+                continue;
+            }
+            index += 1;
+        }
+        blocked_ranges.push(r1);
+    }
+    blocked_ranges
+}
+
+/// The first position, in reading order, with both coordinates between 0
+/// and `search_bound` that no sensor covers.
+fn distress_beacon(sensors: &[Sensor], search_bound: isize) -> Option<(isize, isize)> {
+    (0..=search_bound).find_map(|row| {
+        // The ranges are sorted, so the first gap from column 0 on is the leftmost free column.
+        let column = compute_blocked_ranges(sensors, row)
+            .into_iter()
+            .try_fold(0, |column, (start, end)| if start > column { Err(column) } else { Ok(column.max(end + 1)) })
+            .unwrap_or_else(|column| column);
+        (column <= search_bound).then_some((column, row))
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The row whose blocked positions are counted in part 1.
+    pub row: isize,
+    /// The largest coordinate the distress beacon can have in part 2.
+    pub search_bound: isize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            row: 10,
+            search_bound: 4_000_000,
+        }
+    }
+}
+
+/// Checks that the row and search bound lie within the 32 bits the
+/// coordinates do, so that distances to them and the tuning frequency
+/// cannot overflow.
+fn validate(config: &Config) -> Result<(), Box<dyn Error>> {
+    let range = i32::MIN as isize..=i32::MAX as isize;
+    if !range.contains(&config.row) {
+        return Err(format!("the row must be between {} and {}", range.start(), range.end()).into());
+    }
+    if config.search_bound > *range.end() {
+        return Err(format!("the search bound must be at most {}", range.end()).into());
+    }
+    Ok(())
+}
+
+pub struct Day15;
+
+impl Puzzle for Day15 {
+    const DAY: &'static str = "15";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = Vec<Sensor>;
+    type Output1 = isize;
+    type Output2 = isize;
+    type Config = Config;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(context.lines(input, 0, Sensor::parse)?)
+    }
+
+    fn part1(sensors: &Self::Input, config: &Config) -> Result<isize, Box<dyn Error>> {
+        validate(config)?;
+        let blocked_ranges_row = compute_blocked_ranges(sensors, config.row);
+        let blocked_range_len = blocked_ranges_row
+            .into_iter()
+            .fold(0, |total, (start, end)| total + end - start + 1);
+        // The known beacons in the row sit in covered positions but are beacons, so
+        // subtract them, once each however many sensors report the same one.
+        let beacons_in_row = sensors
+            .iter()
+            .filter(|sensor| sensor.beacon_y == config.row)
+            .map(|sensor| sensor.beacon_x)
+            .collect::<HashSet<_>>();
+        Ok(blocked_range_len - beacons_in_row.len() as isize)
+    }
+
+    fn part2(sensors: &Self::Input, config: &Config) -> Result<isize, Box<dyn Error>> {
+        validate(config)?;
+        let (x, y) = distress_beacon(sensors, config.search_bound).ok_or("no distress beacon found")?;
+        Ok(x * TUNING_FACTOR + y)
+    }
+}
+
+fn describe_ranges(ranges: &[(isize, isize)]) -> String {
+    match ranges.is_empty() {
+        true => "nothing".to_owned(),
+        false => ranges.iter().map(|(start, end)| format!("{start}..={end}")).collect::<Vec<_>>().join(", "),
+    }
+}
+
+impl Explain for Day15 {
+    /// The merged ranges the sensors cover in the row of part 1, and in
+    /// the row where the distress beacon is.
+    fn explain(sensors: &Self::Input, config: &Config) -> Result<Vec<String>, Box<dyn Error>> {
+        validate(config)?;
+        let blocked_ranges_row = compute_blocked_ranges(sensors, config.row);
+        let mut beacons_in_row = sensors
+            .iter()
+            .filter(|sensor| sensor.beacon_y == config.row)
+            .map(|sensor| sensor.beacon_x)
+            .collect::<Vec<_>>();
+        beacons_in_row.sort_unstable();
+        beacons_in_row.dedup();
+        let beacons = match beacons_in_row.is_empty() {
+            true => "no beacons lie in the row".to_owned(),
+            false => {
+                let columns = beacons_in_row.iter().map(isize::to_string).collect::<Vec<_>>();
+                format!("the beacons in the row are at x = {}", columns.join(", "))
+            }
+        };
+        let mut lines = vec![
+            format!("the sensors cover {} of row {}", describe_ranges(&blocked_ranges_row), config.row),
+            beacons,
+            format!("{} positions in the row cannot hold a beacon", Self::part1(sensors, config)?),
+        ];
+        match distress_beacon(sensors, config.search_bound) {
+            Some((x, y)) => {
+                let blocked_ranges = compute_blocked_ranges(sensors, y);
+                lines.push(format!("the sensors cover {} of row {y}, leaving x = {x} free", describe_ranges(&blocked_ranges)));
+                lines.push(format!("the tuning frequency is {x} * {TUNING_FACTOR} + {y} = {}", x * TUNING_FACTOR + y));
+            }
+            None => lines.push(format!("the sensors cover every position up to {}", config.search_bound)),
+        }
+        Ok(lines)
+    }
+}
+
+impl Explore for Day15 {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        ("row Y", "the merged ranges the sensors cover in row Y"),
+        ("sensor I", "where sensor I, counted from 0, and its beacon are, and how far it reaches"),
+    ];
+
+    fn query(sensors: &Self::Input, _config: &Config, command: &str, arguments: &[&str]) -> Result<Vec<String>, Box<dyn Error>> {
+        match command {
+            "row" => {
+                let row = argument::<i32>(arguments, "a row")? as isize;
+                let blocked_ranges = compute_blocked_ranges(sensors, row);
+                let covered = blocked_ranges.iter().map(|(start, end)| end - start + 1).sum::<isize>();
+                Ok(vec![
+                    format!("the sensors cover {}", describe_ranges(&blocked_ranges)),
+                    format!("{covered} positions in all"),
+                ])
+            }
+            "sensor" => {
+                let index = argument::<usize>(arguments, "a sensor")?;
+                let sensor = sensors.get(index).ok_or_else(|| format!("no sensor {index}, there are {}", sensors.len()))?;
+                Ok(vec![format!(
+                    "sensor {index} at ({}, {}) sees a beacon at ({}, {}), {} away",
+                    sensor.sensor_x,
+                    sensor.sensor_y,
+                    sensor.beacon_x,
+                    sensor.beacon_y,
+                    sensor.distance()
+                )])
+            }
+            _ => Err(unknown_command(command)),
+        }
+    }
+}
+
+impl Generate for Day15 {
+    /// A sensor report that leaves exactly one position within the search
+    /// bound uncovered. Four sensors just beyond the corners of the search
+    /// area cover everything but that position, and `size` more sensors
+    /// inside the area stop just short of it.
+    fn generate(rng: &mut InputRng, size: usize, config: &Self::Config) -> String {
+        let bound = config.search_bound.max(1);
+        let distress = (rng.gen_range(0..=bound), rng.gen_range(0..=bound));
+        let mut sensors = [(-1, -1), (-1, 1), (1, -1), (1, 1)]
+            .map(|(sign_x, sign_y)| {
+                let sensor = (distress.0 + sign_x * bound, distress.1 + sign_y * bound);
+                (sensor, (sensor.0 + sign_x * (2 * bound - 1), sensor.1))
+            })
+            .to_vec();
+        while sensors.len() < size + 4 && bound > 1 {
+            let sensor = (rng.gen_range(0..=bound), rng.gen_range(0..=bound));
+            let distance = (sensor.0 - distress.0).abs() + (sensor.1 - distress.1).abs();
+            if distance < 2 {
+                continue;
+            }
+            let radius = rng.gen_range(1..=(bound - 1).min(distance - 1));
+            let offset_x = rng.gen_range(0..=radius);
+            let sign = |rng: &mut InputRng| if rng.gen_bool(0.5) { 1 } else { -1 };
+            let beacon = (
+                sensor.0 + sign(rng) * offset_x,
+                sensor.1 + sign(rng) * (radius - offset_x),
+            );
+            sensors.push((sensor, beacon));
+        }
+        sensors.shuffle(rng);
+        sensors
+            .iter()
+            .map(|(sensor, beacon)| {
+                format!(
+                    "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                    sensor.0, sensor.1, beacon.0, beacon.1
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The most cells per side of the picture of the search area.
+const PICTURE_SIZE: isize = 100;
+
+impl Visualize for Day15 {
+    /// The search area shrunk to at most `PICTURE_SIZE` cells per side, each
+    /// showing the position at its top left corner: coloured by the first
+    /// sensor covering it, `S` for sensors, `B` for beacons and `X` for the
+    /// distress beacon.
+    fn visualize(sensors: &Self::Input, config: &Config) -> Result<Frame, Box<dyn Error>> {
+        validate(config)?;
+        let bound = config.search_bound.max(0);
+        let cell_size = (bound / PICTURE_SIZE + 1) as usize;
+        let side = bound as usize / cell_size + 1;
+        let cell = |(x, y): (isize, isize)| (x / cell_size as isize, y / cell_size as isize);
+        let mut area = Grid::new((0, 0), side, side, ()).map(|(x, y), _| {
+            let position = (x * cell_size as isize, y * cell_size as isize);
+            let covering = sensors.iter().position(|sensor| {
+                (sensor.sensor_x - position.0).abs() + (sensor.sensor_y - position.1).abs() <= sensor.distance()
+            });
+            match covering {
+                Some(index) => Pixel::new('#', Rgb::BLUE.mix(Rgb::GREEN, index as f64 / sensors.len() as f64)),
+                None => Pixel::new('.', Rgb::BLACK),
+            }
+        });
+        for sensor in sensors {
+            if let Some(pixel) = area.get_mut(cell((sensor.beacon_x, sensor.beacon_y))) {
+                *pixel = Pixel::new('B', Rgb::YELLOW);
+            }
+            if let Some(pixel) = area.get_mut(cell((sensor.sensor_x, sensor.sensor_y))) {
+                *pixel = Pixel::new('S', Rgb::WHITE);
+            }
+        }
+        let caption = match distress_beacon(sensors, bound) {
+            Some(distress) => {
+                area[cell(distress)] = Pixel::new('X', Rgb::RED);
+                format!("the distress beacon is at {distress:?}, one cell per {cell_size} by {cell_size} positions")
+            }
+            None => format!("no distress beacon found, one cell per {cell_size} by {cell_size} positions"),
+        };
+        Ok(Frame::new(caption, area))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{
+        Answers, Puzzle,
+        explain::Explain,
+        explore::Explore,
+        generate::{Generate, input_rng},
+        parse::{Mode, ParseContext},
+        visualize::Visualize,
+    };
+    use proptest::{collection::vec, prelude::*};
+
+    use super::{Config, Day15, TUNING_FACTOR};
+
+    const EXAMPLE: &str = "\
+Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+    #[test]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day15::solve_with(EXAMPLE, &Config { row: 10, search_bound: 20 })?;
+        assert_eq!(answers, Answers { part1: 26, part2: 56000011 });
+        Ok(())
+    }
+
+    #[test]
+    fn rows_without_beacons() -> Result<(), Box<dyn Error>> {
+        let sensor = "Sensor at x=0, y=0: closest beacon is at x=2, y=0";
+        assert_eq!(Day15::solve_with(sensor, &Config { row: 0, search_bound: 20 })?.part1, 4);
+        assert_eq!(Day15::solve_with(sensor, &Config { row: 1, search_bound: 20 })?.part1, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn free_columns_at_the_edges() -> Result<(), Box<dyn Error>> {
+        // Searched as far as the real input, the example leaves the columns right of its sensors free.
+        assert_eq!(Day15::solve(EXAMPLE)?.part2, 27 * TUNING_FACTOR);
+        let sensor = "Sensor at x=10, y=0: closest beacon is at x=10, y=5";
+        assert_eq!(Day15::solve_with(sensor, &Config { row: 0, search_bound: 20 })?.part2, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn explain() -> Result<(), Box<dyn Error>> {
+        let explanation = Day15::explain_str(EXAMPLE, &Config { row: 10, search_bound: 20 })?;
+        assert_eq!(explanation, [
+            "the sensors cover -2..=24 of row 10",
+            "the beacons in the row are at x = 2",
+            "26 positions in the row cannot hold a beacon",
+            "the sensors cover -3..=13, 15..=25 of row 11, leaving x = 14 free",
+            "the tuning frequency is 14 * 4000000 + 11 = 56000011",
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn explore() -> Result<(), Box<dyn Error>> {
+        let sensors = Day15::parse(EXAMPLE, &mut ParseContext::new(Day15::DAY, Mode::Strict))?;
+        let query = |command, arguments: &[&str]| Day15::query(&sensors, &Default::default(), command, arguments);
+        assert_eq!(query("row", &["11"])?, ["the sensors cover -3..=13, 15..=25", "28 positions in all"]);
+        assert_eq!(query("sensor", &["6"])?, ["sensor 6 at (8, 7) sees a beacon at (2, 10), 9 away"]);
+        Ok(())
+    }
+
+    #[test]
+    fn visualize() -> Result<(), Box<dyn Error>> {
+        let frame = Day15::draw(EXAMPLE, &Config { row: 10, search_bound: 20 })?;
+        let area = "\
+the distress beacon is at (14, 11), one cell per 1 by 1 positions
+##S##################
+####################S
+#############S#######
+##############SB#####
+#####################
+#####################
+#####################
+########S#######S####
+#####################
+#####################
+##B##################
+S#############X######
+#####################
+#####################
+############S#######S
+#####################
+#########SB##########
+##############S######
+##S##################
+#####################
+##########S######S###";
+        assert_eq!(frame.ascii(), area);
+        Ok(())
+    }
+
+    #[test]
+    fn huge_settings() {
+        let config = Config { row: 10, search_bound: isize::MAX };
+        let error = Day15::draw(EXAMPLE, &config).unwrap_err();
+        assert_eq!(error.to_string(), "the search bound must be at most 2147483647");
+        let error = Day15::solve_with(EXAMPLE, &Config { row: isize::MIN, search_bound: 20 }).unwrap_err();
+        assert_eq!(error.to_string(), "the row must be between -2147483648 and 2147483647");
+    }
+
+    #[test]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day15::solve(&read_to_string(Day15::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: 6030937, part2: 11318723411840 });
+        Ok(())
+    }
+
+    #[test]
+    fn generated() -> Result<(), Box<dyn Error>> {
+        let config = Config { row: 10, search_bound: 1_000 };
+        let input = Day15::generate(&mut input_rng(1), 50, &config);
+        Day15::solve_with(&input, &config)?;
+        Ok(())
+    }
+
+    type Report = Vec<((isize, isize), (isize, isize))>;
+
+    fn covered(report: &Report, (x, y): (isize, isize)) -> bool {
+        report.iter().any(|((sensor_x, sensor_y), (beacon_x, beacon_y))| {
+            let radius = (sensor_x - beacon_x).abs() + (sensor_y - beacon_y).abs();
+            (sensor_x - x).abs() + (sensor_y - y).abs() <= radius
+        })
+    }
+
+    /// Counts the covered positions of the row one by one, leaving out the known beacons.
+    fn blocked_positions(report: &Report, row: isize) -> usize {
+        (-200..=200)
+            .filter(|x| covered(report, (*x, row)) && report.iter().all(|(_, beacon)| *beacon != (*x, row)))
+            .count()
+    }
+
+    /// Searches the whole square row by row for the first position no sensor covers.
+    fn first_uncovered(report: &Report, bound: isize) -> Option<isize> {
+        (0..=bound)
+            .flat_map(|y| (0..=bound).map(move |x| (x, y)))
+            .find(|position| !covered(report, *position))
+            .map(|(x, y)| x * TUNING_FACTOR + y)
+    }
+
+    proptest! {
+        #[test]
+        fn merged_ranges_match_checking_every_position(
+            report in vec(((-10..=30isize, -10..=30isize), (-10..=30isize, -10..=30isize)), 1..8),
+            row in 0..=20isize,
+        ) {
+            let input = report
+                .iter()
+                .map(|((sensor_x, sensor_y), (beacon_x, beacon_y))| {
+                    format!("Sensor at x={sensor_x}, y={sensor_y}: closest beacon is at x={beacon_x}, y={beacon_y}")
+                })
+                .collect::<Vec<_>>();
+            let config = Config { row, search_bound: 20 };
+            let sensors = Day15::parse(&input.join("\n"), &mut ParseContext::new("15", Mode::Strict)).unwrap();
+            prop_assert_eq!(Day15::part1(&sensors, &config).unwrap(), blocked_positions(&report, row) as isize);
+            prop_assert_eq!(Day15::part2(&sensors, &config).ok(), first_uncovered(&report, 20));
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs::{read_to_string, write},
+    io::ErrorKind,
+    path::Path,
+};
+
+use common::Answers;
+
+pub const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.toml");
+
+/// The accepted answers of every day, keyed by day.
+#[derive(Default)]
+pub struct AnswerStore(BTreeMap<String, Answers<String, String>>);
+
+impl AnswerStore {
+    /// Loads the store, treating a missing file as an empty store.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        match read_to_string(path) {
+            Ok(text) => Ok(Self(toml::from_str(&text).map_err(|err| {
+                format!("cannot parse answers file {}: {err}", path.display())
+            })?)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(format!("cannot read answers file {}: {err}", path.display()).into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        write(path, toml::to_string(&self.0)?)
+            .map_err(|err| format!("cannot write answers file {}: {err}", path.display()).into())
+    }
+
+    pub fn get(&self, day: &str) -> Option<&Answers<String, String>> {
+        self.0.get(day)
+    }
+
+    /// Stores the answers for a day, returning the previously recorded ones.
+    pub fn insert(&mut self, day: &str, answers: Answers<String, String>) -> Option<Answers<String, String>> {
+        self.0.insert(day.to_owned(), answers)
+    }
+}
+
+/// The result of checking one part against the store.
+pub enum Verdict {
+    Match,
+    Mismatch { expected: String },
+    Unrecorded,
+}
+
+/// Compares the answers of a day against the recorded ones, part by part.
+pub fn verify(recorded: Option<&Answers<String, String>>, actual: &Answers<String, String>) -> [Verdict; 2] {
+    let check = |expected: Option<&String>, actual: &String| match expected {
+        None => Verdict::Unrecorded,
+        Some(expected) if expected == actual => Verdict::Match,
+        Some(expected) => Verdict::Mismatch { expected: expected.clone() },
+    };
+    [
+        check(recorded.map(|answers| &answers.part1), &actual.part1),
+        check(recorded.map(|answers| &answers.part2), &actual.part2),
+    ]
+}
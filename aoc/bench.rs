@@ -0,0 +1,140 @@
+use std::{
+    error::Error,
+    fs::{read_to_string, write},
+    path::Path,
+    time::Duration,
+};
+
+use common::{Timings, cli::InputArgs};
+use serde::{Deserialize, Serialize};
+
+use crate::Day;
+
+/// Summary statistics over the samples of one phase, in nanoseconds.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Stats {
+    fn from_samples(samples: impl Iterator<Item = Duration>) -> Self {
+        let mut samples = samples.map(|sample| sample.as_nanos() as u64).collect::<Vec<_>>();
+        samples.sort_unstable();
+        Self {
+            min_ns: samples.first().copied().unwrap_or_default(),
+            median_ns: samples.get(samples.len() / 2).copied().unwrap_or_default(),
+            mean_ns: samples.iter().sum::<u64>() / samples.len().max(1) as u64,
+            max_ns: samples.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DayBench {
+    pub day: String,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+    pub total: Stats,
+}
+
+impl DayBench {
+    fn phases(&self) -> [(&'static str, &Stats); 4] {
+        [
+            ("parse", &self.parse),
+            ("part1", &self.part1),
+            ("part2", &self.part2),
+            ("total", &self.total),
+        ]
+    }
+}
+
+/// The machine-readable result of a benchmark run.
+#[derive(Serialize, Deserialize)]
+pub struct BenchReport {
+    pub runs: usize,
+    pub days: Vec<DayBench>,
+}
+
+impl BenchReport {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let text = read_to_string(path)
+            .map_err(|err| format!("cannot read benchmark file {}: {err}", path.display()))?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        write(path, serde_json::to_string_pretty(self)?)
+            .map_err(|err| format!("cannot write benchmark file {}: {err}", path.display()).into())
+    }
+
+    fn find(&self, day: &str) -> Option<&DayBench> {
+        self.days.iter().find(|bench| bench.day == day)
+    }
+}
+
+/// Solves every day `warmup + runs` times, keeping the timings of the last `runs`.
+pub fn bench(days: &[Day], runs: usize, warmup: usize) -> Result<BenchReport, Box<dyn Error>> {
+    let days = days
+        .iter()
+        .map(|day| {
+            let input = InputArgs::default().read(day.input_path)?;
+            let samples = (0..warmup + runs)
+                .map(|_| Ok(day.solve_strict(&input)?.1))
+                .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+            let samples = &samples[warmup..];
+            let stats = |phase: fn(&Timings) -> Duration| Stats::from_samples(samples.iter().map(phase));
+            Ok(DayBench {
+                day: day.name.to_owned(),
+                parse: stats(|timings| timings.parse),
+                part1: stats(|timings| timings.part1),
+                part2: stats(|timings| timings.part2),
+                total: stats(Timings::total),
+            })
+        })
+        .collect::<Result<_, Box<dyn Error>>>()?;
+    Ok(BenchReport { runs, days })
+}
+
+pub fn format_duration(ns: u64) -> String {
+    match ns {
+        0..=999 => format!("{ns}ns"),
+        1_000..=999_999 => format!("{:.1}µs", ns as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.1}ms", ns as f64 / 1e6),
+        _ => format!("{:.2}s", ns as f64 / 1e9),
+    }
+}
+
+/// Prints the report as a table, comparing medians against the baseline if given.
+pub fn print_table(report: &BenchReport, baseline: Option<&BenchReport>) {
+    println!(
+        "{:<4} {:<6} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "day", "phase", "min", "median", "mean", "max", "change"
+    );
+    for bench in &report.days {
+        let previous = baseline.and_then(|baseline| baseline.find(&bench.day)).map(DayBench::phases);
+        for (index, (phase, stats)) in bench.phases().into_iter().enumerate() {
+            let change = previous
+                .map(|previous| previous[index].1)
+                .filter(|previous| previous.median_ns > 0)
+                .map(|previous| {
+                    let ratio = stats.median_ns as f64 / previous.median_ns as f64 - 1.0;
+                    format!("{:+.1}%", ratio * 100.0)
+                })
+                .unwrap_or_default();
+            println!(
+                "{:<4} {:<6} {:>10} {:>10} {:>10} {:>10} {:>10}",
+                bench.day,
+                phase,
+                format_duration(stats.min_ns),
+                format_duration(stats.median_ns),
+                format_duration(stats.mean_ns),
+                format_duration(stats.max_ns),
+                change,
+            );
+        }
+    }
+}
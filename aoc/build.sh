@@ -0,0 +1,3 @@
+#! /bin/bash
+
+cargo run -q -r -- run all
@@ -0,0 +1,556 @@
+use std::{
+    error::Error,
+    fs::write,
+    io::{BufRead, Write, stdin, stdout},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use clap::{Parser, Subcommand};
+use common::{
+    Puzzle, TimedAnswers,
+    animate::{Animate, FrameSink, Output, Recorder},
+    cli::{InputArgs, print_skipped},
+    config::ConfigArgs,
+    explain::Explain,
+    explore::Explore,
+    generate::{Generate, InputRng, input_rng},
+    output::{Format, print_answers, print_explanation},
+    parse::{Mode, ParseContext},
+    visualize::{Frame, Style, Visualize},
+};
+
+use crate::{
+    answers::{ANSWERS_PATH, AnswerStore, Verdict},
+    bench::BenchReport,
+    scaffold::WORKSPACE_PATH,
+};
+
+mod answers;
+mod bench;
+mod run;
+mod scaffold;
+mod watch;
+
+type Solver = fn(&str, &mut ParseContext, &ConfigArgs) -> Result<TimedAnswers<String, String>, Box<dyn Error>>;
+type Explainer = fn(&str, &mut ParseContext, &ConfigArgs) -> Result<Vec<String>, Box<dyn Error>>;
+type Generator = fn(&mut InputRng, usize, &ConfigArgs) -> Result<String, Box<dyn Error>>;
+type Visualizer = fn(&str, &mut ParseContext, &ConfigArgs) -> Result<Frame, Box<dyn Error>>;
+type Animator = fn(&str, &mut ParseContext, &ConfigArgs, &mut FrameSink) -> Result<(), Box<dyn Error>>;
+type Explorer = fn(&str, &mut ParseContext, &ConfigArgs, &mut dyn BufRead, &mut dyn Write) -> Result<(), Box<dyn Error>>;
+
+#[derive(Clone, Copy)]
+struct Day {
+    name: &'static str,
+    input_path: &'static str,
+    solve: Solver,
+    show_config: fn(&ConfigArgs) -> Result<String, Box<dyn Error>>,
+    explain: Explainer,
+    generate: Generator,
+    /// Draws the day's state, for the days that implement `Visualize`.
+    visualize: Option<Visualizer>,
+    /// Records the day's simulation, for the days that implement `Animate`.
+    animate: Option<Animator>,
+    /// Answers queries about the day's input, for the days that implement `Explore`.
+    explore: Option<Explorer>,
+}
+
+impl Day {
+    /// Solves the day with its default configuration, failing on the first malformed input line.
+    fn solve_strict(&self, input: &str) -> Result<TimedAnswers<String, String>, Box<dyn Error>> {
+        (self.solve)(input, &mut ParseContext::new(self.name, Mode::Strict), &ConfigArgs::default())
+    }
+}
+
+fn visualize_day<P: Visualize>(input: &str, context: &mut ParseContext, config: &ConfigArgs) -> Result<Frame, Box<dyn Error>> {
+    let config = config.load::<P::Config>(P::DAY)?;
+    P::visualize(&P::parse(input, context)?, &config)
+}
+
+fn animate_day<P: Animate>(
+    input: &str,
+    context: &mut ParseContext,
+    config: &ConfigArgs,
+    sink: &mut FrameSink,
+) -> Result<(), Box<dyn Error>> {
+    let config = config.load::<P::Config>(P::DAY)?;
+    P::animate(&P::parse(input, context)?, &config, sink)
+}
+
+fn explore_day<P: Explore>(
+    input: &str,
+    context: &mut ParseContext,
+    config: &ConfigArgs,
+    reader: &mut dyn BufRead,
+    writer: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    let config = config.load::<P::Config>(P::DAY)?;
+    P::repl(&P::parse(input, context)?, &config, reader, writer)
+}
+
+/// Registers a day, followed by any of `visualize`, `animate` and
+/// `explore` for the days that implement those traits.
+macro_rules! day {
+    (@feature $puzzle:ty, visualize) => {
+        Some(visualize_day::<$puzzle> as Visualizer)
+    };
+    (@feature $puzzle:ty, animate) => {
+        Some(animate_day::<$puzzle> as Animator)
+    };
+    (@feature $puzzle:ty, explore) => {
+        Some(explore_day::<$puzzle> as Explorer)
+    };
+    (@base $puzzle:ty) => {
+        Day {
+            name: <$puzzle>::DAY,
+            input_path: <$puzzle>::INPUT_PATH,
+            solve: |input, context, config| {
+                let config = config.load::<<$puzzle as Puzzle>::Config>(<$puzzle>::DAY)?;
+                let (answers, timings) = <$puzzle>::solve_timed(input, context, &config)?;
+                Ok((answers.to_strings(), timings))
+            },
+            show_config: |config| {
+                let config = config.load::<<$puzzle as Puzzle>::Config>(<$puzzle>::DAY)?;
+                Ok(toml::to_string(&config)?)
+            },
+            explain: |input, context, config| {
+                let config = config.load::<<$puzzle as Puzzle>::Config>(<$puzzle>::DAY)?;
+                <$puzzle>::explain(&<$puzzle>::parse(input, context)?, &config)
+            },
+            generate: |rng, size, config| {
+                let config = config.load::<<$puzzle as Puzzle>::Config>(<$puzzle>::DAY)?;
+                Ok(<$puzzle>::generate(rng, size, &config))
+            },
+            visualize: None,
+            animate: None,
+            explore: None,
+        }
+    };
+    ($puzzle:ty $(, $feature:ident)*) => {
+        Day {
+            $($feature: day!(@feature $puzzle, $feature),)*
+            ..day!(@base $puzzle)
+        }
+    };
+}
+
+const DAYS: [Day; 15] = [
+    day!(day01::Day01),
+    day!(day02::Day02),
+    day!(day03::Day03),
+    day!(day04::Day04),
+    day!(day05::Day05, animate),
+    day!(day06::Day06),
+    day!(day07::Day07, explore),
+    day!(day08::Day08, visualize),
+    day!(day09::Day09, visualize, animate),
+    day!(day10::Day10, visualize, animate),
+    day!(day11::Day11, animate, explore),
+    day!(day12::Day12, visualize),
+    day!(day13::Day13, explore),
+    day!(day14::Day14, visualize, animate),
+    day!(day15::Day15, visualize, explore),
+];
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs a single day (e.g. `07`), or `all` days concurrently followed by a combined report.
+    Run {
+        /// The day to run, e.g. `07`, or `all`
+        day: String,
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        config: ConfigArgs,
+        /// How to print the answers
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+        /// How many days to run at once when running all of them [default: one per CPU]
+        #[arg(long)]
+        jobs: Option<usize>,
+        /// Print the intermediate values the answers of a single day are built from before them
+        #[arg(long)]
+        explain: bool,
+    },
+    /// Creates the crate of a new day from a template and registers it with the workspace and the runner.
+    NewDay {
+        /// The number of the new day, e.g. `16`
+        day: u8,
+        /// The workspace to add the day to
+        #[arg(long, default_value = WORKSPACE_PATH, hide = true)]
+        root: PathBuf,
+    },
+    /// Prints the settings of a day, or of all days, as a config file.
+    Config {
+        /// The day to show, e.g. `07`, or `all`
+        #[arg(default_value = "all")]
+        day: String,
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+    /// Writes a random input for a day, the same one every time for the same seed.
+    Generate {
+        /// The day to generate an input for, e.g. `07`
+        day: String,
+        /// How large the input should be, in lines, blocks or rows depending on the day
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+        /// The seed of the random numbers [default: taken from the clock and printed]
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Where to write the input [default: standard output]
+        #[arg(long)]
+        output: Option<PathBuf>,
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+    /// Draws the state the solution of a day works through, as text or as an image.
+    Visualize {
+        /// The day to draw, e.g. `14`
+        day: String,
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        config: ConfigArgs,
+        /// How to draw it [default: taken from the extension of the output file, or coloured text]
+        #[arg(long, value_enum)]
+        style: Option<Style>,
+        /// Where to write the drawing [default: standard output]
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// How many pixels wide and high every cell is in images
+        #[arg(long, default_value_t = 4)]
+        scale: usize,
+    },
+    /// Plays the simulation of a day step by step in the terminal, or writes its frames to files or a GIF.
+    Animate {
+        /// The day to animate, e.g. `14`
+        day: String,
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        config: ConfigArgs,
+        /// How many frames to show per second
+        #[arg(long, default_value_t = 10.0)]
+        fps: f64,
+        /// Only keep every Nth frame, and the last one
+        #[arg(long, default_value_t = 1, value_name = "N")]
+        every: usize,
+        /// A directory to write the frames to as numbered files, instead of playing them
+        #[arg(long, value_name = "DIRECTORY", conflicts_with = "gif")]
+        frames: Option<PathBuf>,
+        /// A file to write the frames to as an animated GIF, instead of playing them
+        #[arg(long, value_name = "FILE")]
+        gif: Option<PathBuf>,
+        /// How to draw the frames [default: coloured text when playing them, PNG images when writing files]
+        #[arg(long, value_enum)]
+        style: Option<Style>,
+        /// How many pixels wide and high every cell is in images
+        #[arg(long, default_value_t = 4)]
+        scale: usize,
+    },
+    /// Loads the input of a day once and answers queries about it, read line by line from standard input.
+    Repl {
+        /// The day to explore, e.g. `07`
+        day: String,
+        #[command(flatten)]
+        input: InputArgs,
+        #[command(flatten)]
+        config: ConfigArgs,
+    },
+    /// Builds and runs a day whenever its sources or input change, comparing the answers and timings with the previous run.
+    Watch {
+        /// The day to watch, e.g. `07`
+        day: String,
+        /// How often to look for changes, in milliseconds
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+        /// The workspace the day belongs to
+        #[arg(long, default_value = WORKSPACE_PATH, hide = true)]
+        root: PathBuf,
+    },
+    /// Checks the answers of a day, or of all days, against the recorded ones.
+    Verify {
+        /// The day to verify, e.g. `07`, or `all`
+        #[arg(default_value = "all")]
+        day: String,
+        /// The answers file to compare against
+        #[arg(long, default_value = ANSWERS_PATH)]
+        answers: PathBuf,
+    },
+    /// Accepts the current answers of a day, or of all days, into the answers file.
+    Record {
+        /// The day to record, e.g. `07`, or `all`
+        #[arg(default_value = "all")]
+        day: String,
+        /// The answers file to update
+        #[arg(long, default_value = ANSWERS_PATH)]
+        answers: PathBuf,
+    },
+    /// Times parsing and both parts of a day, or of all days, over repeated runs.
+    Bench {
+        /// The day to benchmark, e.g. `07`, or `all`
+        #[arg(default_value = "all")]
+        day: String,
+        /// How many measured runs to take per day
+        #[arg(long, default_value_t = 5)]
+        runs: usize,
+        /// How many unmeasured runs to make first
+        #[arg(long, default_value_t = 1)]
+        warmup: usize,
+        /// Where to write the machine-readable results
+        #[arg(long, default_value = "bench.json")]
+        output: PathBuf,
+        /// A previous results file to compare the medians against
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+    },
+}
+
+fn select_days(day: &str) -> Result<Vec<Day>, Box<dyn Error>> {
+    if day == "all" {
+        return Ok(DAYS.to_vec());
+    }
+    let day = format!("{:0>2}", day);
+    let day = DAYS
+        .iter()
+        .find(|Day { name, .. }| *name == day)
+        .ok_or_else(|| format!("no solution for day {day}"))?;
+    Ok(vec![*day])
+}
+
+fn verify(day: &str, answers_path: &Path) -> Result<ExitCode, Box<dyn Error>> {
+    let store = AnswerStore::load(answers_path)?;
+    let (mut matched, mut mismatched, mut unrecorded, mut failed) = (0, 0, 0, 0);
+    for day in select_days(day)? {
+        let actual = match InputArgs::default().read(day.input_path).and_then(|input| day.solve_strict(&input)) {
+            Ok((actual, _)) => actual,
+            Err(err) => {
+                println!("2022-12-{} FAILED: {err}", day.name);
+                failed += 1;
+                continue;
+            }
+        };
+        let verdicts = answers::verify(store.get(day.name), &actual);
+        for (part, (verdict, answer)) in verdicts.iter().zip([&actual.part1, &actual.part2]).enumerate() {
+            let status = match verdict {
+                Verdict::Match => {
+                    matched += 1;
+                    "ok".to_owned()
+                }
+                Verdict::Mismatch { expected } => {
+                    mismatched += 1;
+                    format!("MISMATCH (expected {expected:?}, got {answer:?})")
+                }
+                Verdict::Unrecorded => {
+                    unrecorded += 1;
+                    format!("unrecorded (got {answer:?})")
+                }
+            };
+            println!("2022-12-{} Part {}: {status}", day.name, part + 1);
+        }
+    }
+    println!("{matched} ok, {mismatched} mismatched, {unrecorded} unrecorded, {failed} failed");
+    Ok(if mismatched + failed > 0 { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
+
+fn record(day: &str, answers_path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut store = AnswerStore::load(answers_path)?;
+    for day in select_days(day)? {
+        let (actual, _) = day.solve_strict(&InputArgs::default().read(day.input_path)?)?;
+        let status = match store.insert(day.name, actual.clone()) {
+            None => "recorded",
+            Some(previous) if previous == actual => "unchanged",
+            Some(_) => "updated",
+        };
+        println!("2022-12-{}: {status}", day.name);
+    }
+    store.save(answers_path)
+}
+
+fn visualize(
+    day: &str,
+    input: &InputArgs,
+    config: &ConfigArgs,
+    style: Option<Style>,
+    output: Option<PathBuf>,
+    scale: usize,
+) -> Result<(), Box<dyn Error>> {
+    let [day] = select_days(day)?[..] else {
+        return Err("only a single day can be drawn at a time".into());
+    };
+    let visualize = day.visualize.ok_or_else(|| format!("day {} cannot be drawn", day.name))?;
+    if scale == 0 {
+        return Err("the scale must be at least 1".into());
+    }
+    let style = match (style, &output) {
+        (Some(style), _) => style,
+        (None, None) => Style::Ansi,
+        (None, Some(path)) => Style::from_path(path)
+            .ok_or_else(|| format!("cannot tell how to draw {} from its extension, use --style", path.display()))?,
+    };
+    let mut context = ParseContext::new(day.name, input.mode());
+    let frame = visualize(&input.read(day.input_path)?, &mut context, config);
+    print_skipped(&context);
+    let mut drawing = Vec::new();
+    frame?.write(&mut drawing, style, scale)?;
+    match output {
+        Some(path) => write(&path, drawing).map_err(|err| format!("cannot write {}: {err}", path.display()))?,
+        None => stdout().write_all(&drawing)?,
+    }
+    Ok(())
+}
+
+/// The settings of `aoc animate` besides the day, its input and its configuration.
+struct AnimateArgs {
+    fps: f64,
+    every: usize,
+    frames: Option<PathBuf>,
+    gif: Option<PathBuf>,
+    style: Option<Style>,
+    scale: usize,
+}
+
+fn animate(day: &str, input: &InputArgs, config: &ConfigArgs, args: AnimateArgs) -> Result<(), Box<dyn Error>> {
+    let [day] = select_days(day)?[..] else {
+        return Err("only a single day can be animated at a time".into());
+    };
+    let animate = day.animate.ok_or_else(|| format!("day {} cannot be animated", day.name))?;
+    if !(args.fps.is_finite() && args.fps > 0.0) {
+        return Err("the frames per second must be a positive number".into());
+    }
+    if args.scale == 0 || args.every == 0 {
+        return Err("the scale and --every must be at least 1".into());
+    }
+    let output = match (args.frames, args.gif) {
+        (Some(directory), _) => Output::Files {
+            directory,
+            style: args.style.unwrap_or(Style::Png),
+            scale: args.scale,
+        },
+        (None, Some(path)) => Output::Gif {
+            path,
+            scale: args.scale,
+            delay: (100.0 / args.fps).round().clamp(1.0, u16::MAX as f64) as u16,
+        },
+        (None, None) => Output::Terminal {
+            style: args.style.unwrap_or(Style::Ansi),
+            delay: Duration::from_secs_f64(1.0 / args.fps),
+        },
+    };
+    let mut recorder = Recorder::new(output);
+    let mut keep = |frame| recorder.record(frame);
+    let mut context = ParseContext::new(day.name, input.mode());
+    let result = animate(&input.read(day.input_path)?, &mut context, config, &mut FrameSink::new(args.every, &mut keep));
+    print_skipped(&context);
+    result?;
+    let written = recorder.finish()?;
+    eprintln!("{written} frames");
+    Ok(())
+}
+
+fn repl(day: &str, input: &InputArgs, config: &ConfigArgs) -> Result<(), Box<dyn Error>> {
+    let [day] = select_days(day)?[..] else {
+        return Err("only a single day can be explored at a time".into());
+    };
+    let explore = day.explore.ok_or_else(|| format!("day {} has no commands to explore it with", day.name))?;
+    if input.input.as_ref().is_some_and(|path| path.as_os_str() == "-") {
+        return Err("the commands are read from standard input, so the puzzle input cannot be".into());
+    }
+    let mut context = ParseContext::new(day.name, input.mode());
+    let text = input.read(day.input_path)?;
+    explore(&text, &mut context, config, &mut stdin().lock(), &mut stdout().lock())
+}
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    match Cli::parse().command {
+        Command::Run { day, input, config, format, jobs, explain } => {
+            let days = select_days(&day)?;
+            if days.len() > 1 && !input.is_default() {
+                return Err("an input can only be given when running a single day".into());
+            }
+            if days.len() > 1 && !config.overrides.is_empty() {
+                return Err("settings can only be overridden when running a single day".into());
+            }
+            if days.len() > 1 && explain {
+                return Err("only the answers of a single day can be explained".into());
+            }
+            if let [day] = days[..] {
+                let text = input.read(day.input_path)?;
+                let mut context = ParseContext::new(day.name, input.mode());
+                let result = (day.solve)(&text, &mut context, &config);
+                print_skipped(&context);
+                let (answers, timings) = result?;
+                if explain {
+                    let explanation = (day.explain)(&text, &mut ParseContext::new(day.name, input.mode()), &config)?;
+                    print_explanation(day.name, &explanation, format);
+                }
+                print_answers(day.name, &answers, &timings, format);
+            } else {
+                let report = run::run_days(&days, &input, &config, jobs)?;
+                run::print_report(&report, format);
+                if report.failed() > 0 {
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+        }
+        Command::NewDay { day, root } => scaffold::new_day(&root, day)?,
+        Command::Config { day, config } => {
+            for day in select_days(&day)? {
+                let settings = (day.show_config)(&config)?;
+                if !settings.is_empty() {
+                    println!("[{}]\n{settings}", day.name);
+                }
+            }
+        }
+        Command::Generate { day, size, seed, output, config } => {
+            let [day] = select_days(&day)?[..] else {
+                return Err("inputs can only be generated for a single day".into());
+            };
+            let seed = seed.unwrap_or_else(|| {
+                let seed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64;
+                eprintln!("seed {seed}");
+                seed
+            });
+            let input = (day.generate)(&mut input_rng(seed), size, &config)?;
+            match output {
+                Some(path) => write(&path, input).map_err(|err| format!("cannot write {}: {err}", path.display()))?,
+                None => println!("{input}"),
+            }
+        }
+        Command::Visualize { day, input, config, style, output, scale } => {
+            visualize(&day, &input, &config, style, output, scale)?
+        }
+        Command::Animate { day, input, config, fps, every, frames, gif, style, scale } => {
+            let args = AnimateArgs { fps, every, frames, gif, style, scale };
+            animate(&day, &input, &config, args)?
+        }
+        Command::Repl { day, input, config } => repl(&day, &input, &config)?,
+        Command::Watch { day, interval, root } => {
+            let [day] = select_days(&day)?[..] else {
+                return Err("only a single day can be watched at a time".into());
+            };
+            watch::watch(&day, &root, Duration::from_millis(interval.max(1)))?
+        }
+        Command::Verify { day, answers } => return verify(&day, &answers),
+        Command::Record { day, answers } => record(&day, &answers)?,
+        Command::Bench { day, runs, warmup, output, baseline } => {
+            if runs == 0 {
+                return Err("at least one run is needed".into());
+            }
+            let baseline = baseline.as_deref().map(BenchReport::load).transpose()?;
+            let report = bench::bench(&select_days(&day)?, runs, warmup)?;
+            bench::print_table(&report, baseline.as_ref());
+            report.save(&output)?;
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
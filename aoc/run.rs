@@ -0,0 +1,112 @@
+use std::{
+    error::Error,
+    time::{Duration, Instant},
+};
+
+use common::{
+    TimedAnswers,
+    cli::InputArgs,
+    config::ConfigArgs,
+    output::{Format, print_answers},
+    parse::{ParseContext, ParseError},
+};
+use rayon::{ThreadPoolBuilder, prelude::*};
+use serde::Serialize;
+
+use crate::{Day, bench::format_duration};
+
+/// The outcome of running one day as part of several.
+pub struct DayRun {
+    pub day: &'static str,
+    pub result: Result<TimedAnswers<String, String>, String>,
+    pub skipped: Vec<ParseError>,
+}
+
+/// All days of a run together with how long the run took as a whole.
+pub struct RunReport {
+    pub runs: Vec<DayRun>,
+    pub elapsed: Duration,
+}
+
+impl RunReport {
+    pub fn failed(&self) -> usize {
+        self.runs.iter().filter(|run| run.result.is_err()).count()
+    }
+}
+
+fn run_day(day: &Day, input: &InputArgs, config: &ConfigArgs) -> DayRun {
+    let mut context = ParseContext::new(day.name, input.mode());
+    let result = input
+        .read(day.input_path)
+        .and_then(|text| (day.solve)(&text, &mut context, config))
+        .map_err(|err| err.to_string());
+    DayRun {
+        day: day.name,
+        result,
+        skipped: context.skipped().to_vec(),
+    }
+}
+
+/// Runs the days concurrently on `jobs` threads, or one per CPU if not given,
+/// keeping the results in the order of the days.
+pub fn run_days(days: &[Day], input: &InputArgs, config: &ConfigArgs, jobs: Option<usize>) -> Result<RunReport, Box<dyn Error>> {
+    let pool = ThreadPoolBuilder::new().num_threads(jobs.unwrap_or_default()).build()?;
+    let start = Instant::now();
+    let runs = pool.install(|| days.par_iter().map(|day| run_day(day, input, config)).collect());
+    Ok(RunReport { runs, elapsed: start.elapsed() })
+}
+
+/// A failed day, as written by `--format json`.
+#[derive(Serialize)]
+struct ErrorRecord<'r> {
+    day: &'r str,
+    error: &'r str,
+}
+
+/// Prints the answers of every day, followed by a table of timings and
+/// errors in text format.
+pub fn print_report(report: &RunReport, format: Format) {
+    for run in &report.runs {
+        for error in &run.skipped {
+            eprintln!("skipped {error}");
+        }
+        match (&run.result, format) {
+            (Ok((answers, timings)), _) => print_answers(run.day, answers, timings, format),
+            (Err(error), Format::Text) => println!("2022-12-{} FAILED: {error}", run.day),
+            (Err(error), Format::Json) => {
+                let record = ErrorRecord { day: run.day, error };
+                println!("{}", serde_json::to_string(&record).unwrap_or_default());
+            }
+        }
+    }
+    if format == Format::Text {
+        print_table(report);
+    }
+}
+
+fn print_table(report: &RunReport) {
+    println!();
+    println!("{:<4} {:<7} {:>10} {:>10} {:>10} {:>10}", "day", "status", "parse", "part1", "part2", "total");
+    let mut summed = Duration::ZERO;
+    for run in &report.runs {
+        match &run.result {
+            Ok((_, timings)) => {
+                summed += timings.total();
+                let phases = [timings.parse, timings.part1, timings.part2, timings.total()]
+                    .map(|duration| format_duration(duration.as_nanos() as u64));
+                println!(
+                    "{:<4} {:<7} {:>10} {:>10} {:>10} {:>10}",
+                    run.day, "ok", phases[0], phases[1], phases[2], phases[3]
+                );
+            }
+            Err(error) => println!("{:<4} {:<7} {error}", run.day, "FAILED"),
+        }
+    }
+    println!(
+        "{} ok, {} failed in {} (summed time of all days: {})",
+        report.runs.len() - report.failed(),
+        report.failed(),
+        format_duration(report.elapsed.as_nanos() as u64),
+        format_duration(summed.as_nanos() as u64),
+    );
+}
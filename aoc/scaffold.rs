@@ -0,0 +1,244 @@
+use std::{
+    error::Error,
+    fs::{self, create_dir, read_to_string, write},
+    os::unix::fs::PermissionsExt,
+    path::Path,
+};
+
+pub const WORKSPACE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+const CARGO_TOML: &str = r#"[package]
+name = "day{day}"
+version = "1.0.0"
+edition = "2024"
+publish = false
+
+[lib]
+path = "lib.rs"
+
+[[bin]]
+name = "day{day}"
+path = "main.rs"
+
+[dependencies]
+common = { path = "../common" }
+"#;
+
+const GITIGNORE: &str = "Cargo.lock\ntarget/\n";
+
+const BUILD_SH: &str = "#! /bin/bash\n\ncargo run -q -r\n";
+
+const MAIN_RS: &str = "use std::error::Error;
+
+use day{day}::Day{day};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    common::cli::main::<Day{day}>()
+}
+";
+
+const LIB_RS: &str = r#"use std::error::Error;
+
+use common::{
+    Puzzle,
+    config::NoConfig,
+    explain::Explain,
+    generate::{Generate, InputRng},
+    parse::ParseContext,
+};
+
+pub struct Day{day};
+
+impl Puzzle for Day{day} {
+    const DAY: &'static str = "{day}";
+    const INPUT_PATH: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+    type Input = Vec<String>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Config = NoConfig;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(context.lines(input, 0, |line| Ok(line.to_owned()))?)
+    }
+
+    fn part1(_input: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        Err("part 1 is not solved yet".into())
+    }
+
+    fn part2(_input: &Self::Input, _config: &Self::Config) -> Result<usize, Box<dyn Error>> {
+        Err("part 2 is not solved yet".into())
+    }
+}
+
+impl Explain for Day{day} {
+    fn explain(_input: &Self::Input, _config: &Self::Config) -> Result<Vec<String>, Box<dyn Error>> {
+        Ok(Vec::new())
+    }
+}
+
+impl Generate for Day{day} {
+    fn generate(_rng: &mut InputRng, _size: usize, _config: &Self::Config) -> String {
+        String::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, fs::read_to_string};
+
+    use common::{Answers, Puzzle};
+
+    use super::Day{day};
+
+    const EXAMPLE: &str = "\
+";
+
+    #[test]
+    #[ignore = "the example and its answers are not filled in yet"]
+    fn example() -> Result<(), Box<dyn Error>> {
+        let answers = Day{day}::solve(EXAMPLE)?;
+        assert_eq!(answers, Answers { part1: 0, part2: 0 });
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "the answers are not filled in yet"]
+    fn input() -> Result<(), Box<dyn Error>> {
+        let answers = Day{day}::solve(&read_to_string(Day{day}::INPUT_PATH)?)?;
+        assert_eq!(answers, Answers { part1: 0, part2: 0 });
+        Ok(())
+    }
+}
+"#;
+
+/// Replaces the lines from the first to the last one matching `is_entry`
+/// with the sorted entries plus `entry`.
+fn insert_sorted(text: &str, is_entry: impl Fn(&str) -> bool, entry: String) -> Result<String, Box<dyn Error>> {
+    let lines = text.split('\n').collect::<Vec<_>>();
+    let first = lines.iter().position(|line| is_entry(line)).ok_or("no entries to extend")?;
+    let last = lines.iter().rposition(|line| is_entry(line)).unwrap_or(first);
+    let mut entries = lines[first..=last].iter().map(|line| line.to_string()).collect::<Vec<_>>();
+    if entries.contains(&entry) {
+        return Err(format!("{} is already registered", entry.trim()).into());
+    }
+    entries.push(entry);
+    entries.sort();
+    Ok([&lines[..first], &entries.iter().map(String::as_str).collect::<Vec<_>>(), &lines[last + 1..]]
+        .concat()
+        .join("\n"))
+}
+
+/// Adds the day to the members of the workspace manifest.
+fn register_member(manifest: &str, day: &str) -> Result<String, Box<dyn Error>> {
+    let is_day_list = |line: &str| {
+        let line = line.trim().trim_end_matches(',');
+        !line.is_empty()
+            && line.split(", ").all(|member| {
+                let digits = member.strip_prefix('"').and_then(|member| member.strip_suffix('"'));
+                digits.is_some_and(|digits| digits.len() == 2 && digits.bytes().all(|byte| byte.is_ascii_digit()))
+            })
+    };
+    let line = manifest
+        .split('\n')
+        .find(|line| is_day_list(line))
+        .ok_or("no list of days in the workspace members")?;
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let mut members = line.trim().trim_end_matches(',').split(", ").collect::<Vec<_>>();
+    let member = format!("\"{day}\"");
+    if members.contains(&member.as_str()) {
+        return Err(format!("day {day} is already a workspace member").into());
+    }
+    members.push(&member);
+    members.sort();
+    Ok(manifest.replacen(line, &format!("{indent}{},", members.join(", ")), 1))
+}
+
+/// Adds the day to the runner's dependencies.
+fn register_dependency(manifest: &str, day: &str) -> Result<String, Box<dyn Error>> {
+    let is_day = |line: &str| line.starts_with("day") && line.contains("path = \"../");
+    insert_sorted(manifest, is_day, format!("day{day} = {{ path = \"../{day}\" }}"))
+}
+
+/// Adds the day to the runner's list of days, adjusting the length of the list.
+fn register_day(main: &str, day: &str) -> Result<String, Box<dyn Error>> {
+    let is_day = |line: &str| line.trim_start().starts_with("day!(");
+    let main = insert_sorted(main, is_day, format!("    day!(day{day}::Day{day}),"))?;
+    let count = main.split('\n').filter(|line| is_day(line)).count();
+    let start = main.find("const DAYS: [Day; ").ok_or("no list of days in the runner")?;
+    let end = start + main[start..].find(']').ok_or("no list of days in the runner")?;
+    Ok(format!("{}const DAYS: [Day; {count}{}", &main[..start], &main[end..]))
+}
+
+fn update(path: &Path, edit: impl Fn(&str) -> Result<String, Box<dyn Error>>) -> Result<(), Box<dyn Error>> {
+    let text = read_to_string(path).map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+    write(path, edit(&text)?).map_err(|err| format!("cannot write {}: {err}", path.display()).into())
+}
+
+/// Creates the crate of a new day in the workspace at `root` and registers it
+/// with the workspace and the runner.
+pub fn new_day(root: &Path, day: u8) -> Result<(), Box<dyn Error>> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("there is no day {day} in Advent of Code").into());
+    }
+    let day = format!("{day:02}");
+    let directory = root.join(&day);
+    if directory.exists() {
+        return Err(format!("{} already exists", directory.display()).into());
+    }
+
+    // Check the registrations before touching anything, so a failure leaves no half-made day behind.
+    let workspace = register_member(&read_to_string(root.join("Cargo.toml"))?, &day)?;
+    register_dependency(&read_to_string(root.join("aoc/Cargo.toml"))?, &day)?;
+    register_day(&read_to_string(root.join("aoc/main.rs"))?, &day)?;
+
+    create_dir(&directory)?;
+    let fill = |template: &str| template.replace("{day}", &day);
+    write(directory.join("Cargo.toml"), fill(CARGO_TOML))?;
+    write(directory.join(".gitignore"), GITIGNORE)?;
+    write(directory.join("build.sh"), BUILD_SH)?;
+    fs::set_permissions(directory.join("build.sh"), fs::Permissions::from_mode(0o775))?;
+    write(directory.join("main.rs"), fill(MAIN_RS))?;
+    write(directory.join("lib.rs"), fill(LIB_RS))?;
+    write(directory.join("input.txt"), "")?;
+
+    write(root.join("Cargo.toml"), workspace)?;
+    update(&root.join("aoc/Cargo.toml"), |manifest| register_dependency(manifest, &day))?;
+    update(&root.join("aoc/main.rs"), |main| register_day(main, &day))?;
+    println!("created day {day} in {}", directory.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{register_day, register_dependency, register_member};
+
+    #[test]
+    fn member_is_inserted_in_order() {
+        let manifest = "[workspace]\nmembers = [\n    \"01\", \"02\", \"04\",\n    \"aoc\",\n]";
+        assert_eq!(
+            register_member(manifest, "03").unwrap(),
+            "[workspace]\nmembers = [\n    \"01\", \"02\", \"03\", \"04\",\n    \"aoc\",\n]"
+        );
+        assert!(register_member(manifest, "02").is_err());
+    }
+
+    #[test]
+    fn dependency_is_inserted_in_order() {
+        let manifest = "clap = \"1\"\nday01 = { path = \"../01\" }\nday02 = { path = \"../02\" }\nserde = \"1\"";
+        assert_eq!(
+            register_dependency(manifest, "16").unwrap(),
+            "clap = \"1\"\nday01 = { path = \"../01\" }\nday02 = { path = \"../02\" }\nday16 = { path = \"../16\" }\nserde = \"1\""
+        );
+    }
+
+    #[test]
+    fn day_is_added_to_the_runner() {
+        let main = "const DAYS: [Day; 2] = [\n    day!(day01::Day01),\n    day!(day02::Day02),\n];";
+        assert_eq!(
+            register_day(main, "16").unwrap(),
+            "const DAYS: [Day; 3] = [\n    day!(day01::Day01),\n    day!(day02::Day02),\n    day!(day16::Day16),\n];"
+        );
+        assert!(register_day(main, "01").is_err());
+    }
+}
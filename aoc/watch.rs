@@ -0,0 +1,182 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs::{metadata, read_dir},
+    path::{Path, PathBuf},
+    process::Command,
+    thread::sleep,
+    time::{Duration, SystemTime},
+};
+
+use common::output::AnswerRecord;
+
+use crate::{Day, bench::format_duration};
+
+/// When each watched file was last modified, or `None` while it is missing.
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// The sources and the input of a day.
+fn snapshot(directory: &Path) -> Result<Snapshot, Box<dyn Error>> {
+    let mut paths = Vec::new();
+    for entry in read_dir(directory).map_err(|err| format!("cannot read {}: {err}", directory.display()))? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "rs") {
+            paths.push(path);
+        }
+    }
+    paths.extend([directory.join("Cargo.toml"), directory.join("input.txt")]);
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let modified = metadata(&path).and_then(|metadata| metadata.modified()).ok();
+            (path, modified)
+        })
+        .collect())
+}
+
+/// The files that were added, removed or modified between two snapshots.
+fn changed_files(previous: &Snapshot, current: &Snapshot) -> Vec<PathBuf> {
+    let mut paths = previous
+        .iter()
+        .filter(|(path, modified)| current.get(*path) != Some(modified))
+        .chain(current.iter().filter(|(path, _)| !previous.contains_key(*path)))
+        .map(|(path, _)| path.clone())
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// Builds and runs the day's own binary, returning the answers it prints,
+/// or why it could not be built or run.
+fn run(day: &Day, root: &Path) -> Result<Vec<AnswerRecord>, String> {
+    let output = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--package", &format!("day{}", day.name), "--"])
+        .args(["--format", "json"])
+        .current_dir(root)
+        .output()
+        .map_err(|err| format!("cannot run cargo: {err}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim_end().to_owned());
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).map_err(|err| format!("cannot read the answers: {err}")))
+        .collect()
+}
+
+/// The answer as shown in one line, with multi-line drawings replaced by
+/// the letters they spell where possible.
+fn display_answer(record: &AnswerRecord) -> String {
+    record.text.clone().unwrap_or_else(|| record.answer.replace('\n', "⏎"))
+}
+
+/// One line per part, comparing its answer and timing with the previous run.
+fn describe_changes(previous: Option<&[AnswerRecord]>, current: &[AnswerRecord]) -> Vec<String> {
+    current
+        .iter()
+        .map(|record| {
+            let answer = display_answer(record);
+            let duration = format_duration(record.duration);
+            let before = previous.and_then(|previous| previous.iter().find(|before| before.part == record.part));
+            let Some(before) = before else {
+                return format!("Part {}: {answer} in {duration}", record.part);
+            };
+            let answer = match before.answer == record.answer {
+                true => format!("{answer} (unchanged)"),
+                false => format!("{} -> {answer}", display_answer(before)),
+            };
+            let change = match before.duration {
+                0 => String::new(),
+                ns => format!(" ({:+.1}%)", (record.duration as f64 / ns as f64 - 1.0) * 100.0),
+            };
+            format!("Part {}: {answer} in {} -> {duration}{change}", record.part, format_duration(before.duration))
+        })
+        .collect()
+}
+
+/// Runs the day, then again whenever its sources or input change, until interrupted.
+pub fn watch(day: &Day, root: &Path, interval: Duration) -> Result<(), Box<dyn Error>> {
+    let directory = root.join(day.name);
+    let mut files = snapshot(&directory)?;
+    let mut previous = None;
+    loop {
+        println!("2022-12-{}: building and running", day.name);
+        match run(day, root) {
+            Ok(answers) => {
+                for line in describe_changes(previous.as_deref(), &answers) {
+                    println!("2022-12-{} {line}", day.name);
+                }
+                previous = Some(answers);
+            }
+            Err(err) => println!("2022-12-{} FAILED:\n{err}", day.name),
+        }
+        println!("2022-12-{}: waiting for changes", day.name);
+        let changed = loop {
+            sleep(interval);
+            let current = snapshot(&directory)?;
+            let changed = changed_files(&files, &current);
+            files = current;
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+        for path in changed {
+            let path = path.strip_prefix(root).unwrap_or(&path);
+            println!("2022-12-{}: {} changed", day.name, path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        path::PathBuf,
+        time::{Duration, SystemTime},
+    };
+
+    use common::output::AnswerRecord;
+
+    use super::{changed_files, describe_changes};
+
+    fn record(part: u8, answer: &str, duration: u64) -> AnswerRecord {
+        AnswerRecord {
+            day: "07".to_owned(),
+            part,
+            answer: answer.to_owned(),
+            text: None,
+            duration,
+        }
+    }
+
+    #[test]
+    fn answers_and_timings_are_compared() {
+        let first = [record(1, "95437", 2_000_000), record(2, "24933642", 0)];
+        assert_eq!(describe_changes(None, &first), ["Part 1: 95437 in 2.0ms", "Part 2: 24933642 in 0ns"]);
+        let second = [record(1, "95437", 1_500_000), record(2, "42", 300)];
+        assert_eq!(
+            describe_changes(Some(&first), &second),
+            ["Part 1: 95437 (unchanged) in 2.0ms -> 1.5ms (-25.0%)", "Part 2: 24933642 -> 42 in 0ns -> 300ns"]
+        );
+    }
+
+    #[test]
+    fn added_removed_and_modified_files_change() {
+        let time = |seconds| Some(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds));
+        let before = BTreeMap::from([
+            (PathBuf::from("lib.rs"), time(1)),
+            (PathBuf::from("main.rs"), time(1)),
+            (PathBuf::from("old.rs"), time(1)),
+            (PathBuf::from("input.txt"), None),
+        ]);
+        let after = BTreeMap::from([
+            (PathBuf::from("lib.rs"), time(2)),
+            (PathBuf::from("main.rs"), time(1)),
+            (PathBuf::from("new.rs"), time(1)),
+            (PathBuf::from("input.txt"), time(3)),
+        ]);
+        let changed = ["input.txt", "lib.rs", "new.rs", "old.rs"].map(PathBuf::from);
+        assert_eq!(changed_files(&before, &after), changed);
+    }
+}
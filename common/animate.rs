@@ -0,0 +1,254 @@
+use std::{
+    error::Error,
+    fs::{create_dir_all, write},
+    io::{Write, stdout},
+    path::PathBuf,
+    thread::sleep,
+    time::Duration,
+};
+
+use crate::{
+    Puzzle,
+    parse::{Mode, ParseContext},
+    visualize::{Frame, Rgb, Style},
+};
+
+/// Receives the states of an animation one at a time, and draws only
+/// those that are kept: every `every`th one, and the final one. Drawing
+/// every state of a long simulation takes far longer than simulating it.
+pub struct FrameSink<'s> {
+    every: usize,
+    offered: usize,
+    /// Whether the latest state was skipped, so that `finish` has to draw it.
+    skipped: bool,
+    keep: &'s mut dyn FnMut(Frame) -> Result<(), Box<dyn Error>>,
+}
+
+impl<'s> FrameSink<'s> {
+    pub fn new(every: usize, keep: &'s mut dyn FnMut(Frame) -> Result<(), Box<dyn Error>>) -> Self {
+        Self {
+            every: every.max(1),
+            offered: 0,
+            skipped: false,
+            keep,
+        }
+    }
+
+    /// Offers the next state, calling `draw` only if its frame is kept.
+    pub fn push(&mut self, draw: impl FnOnce() -> Frame) -> Result<(), Box<dyn Error>> {
+        let kept = self.offered.is_multiple_of(self.every);
+        self.offered += 1;
+        self.skipped = !kept;
+        if kept { (self.keep)(draw()) } else { Ok(()) }
+    }
+
+    /// Ends the animation with the state offered last, drawing it if it was skipped.
+    pub fn finish(&mut self, draw: impl FnOnce() -> Frame) -> Result<(), Box<dyn Error>> {
+        if self.skipped {
+            self.skipped = false;
+            (self.keep)(draw())?;
+        }
+        Ok(())
+    }
+}
+
+/// Records the states a day's simulation goes through, step by step.
+pub trait Animate: Puzzle {
+    /// Pushes every state to `sink` in order, starting with the initial one,
+    /// and then finishes the sink with the final one. All frames of an
+    /// animation have the same size.
+    fn animate(input: &Self::Input, config: &Self::Config, sink: &mut FrameSink) -> Result<(), Box<dyn Error>>;
+
+    /// Parses the input, failing on the first malformed line, and collects
+    /// every frame of its animation.
+    fn record(input: &str, config: &Self::Config) -> Result<Vec<Frame>, Box<dyn Error>> {
+        let input = Self::parse(input, &mut ParseContext::new(Self::DAY, Mode::Strict))?;
+        let mut frames = Vec::new();
+        let mut keep = |frame| {
+            frames.push(frame);
+            Ok(())
+        };
+        Self::animate(&input, config, &mut FrameSink::new(1, &mut keep))?;
+        Ok(frames)
+    }
+}
+
+/// Where the frames of an animation end up.
+pub enum Output {
+    /// Drawn over each other in the terminal, `delay` apart.
+    Terminal { style: Style, delay: Duration },
+    /// Written to numbered files in `directory`.
+    Files { directory: PathBuf, style: Style, scale: usize },
+    /// Encoded as an animated GIF image, showing every frame for `delay`
+    /// hundredths of a second.
+    Gif { path: PathBuf, scale: usize, delay: u16 },
+}
+
+type GifEncoder = gif::Encoder<Vec<u8>>;
+
+/// Writes the frames of an animation to an output as they come.
+pub struct Recorder {
+    output: Output,
+    written: usize,
+    /// The GIF being encoded, with the size of its frames.
+    gif: Option<(GifEncoder, (u16, u16))>,
+}
+
+impl Recorder {
+    pub fn new(output: Output) -> Self {
+        Self {
+            output,
+            written: 0,
+            gif: None,
+        }
+    }
+
+    /// Completes the output, returning how many frames were written.
+    pub fn finish(mut self) -> Result<usize, Box<dyn Error>> {
+        if let (Some((encoder, _)), Output::Gif { path, .. }) = (self.gif.take(), &self.output) {
+            write(path, encoder.into_inner()?).map_err(|err| format!("cannot write {}: {err}", path.display()))?;
+        }
+        Ok(self.written)
+    }
+
+    pub fn record(&mut self, frame: Frame) -> Result<(), Box<dyn Error>> {
+        match &self.output {
+            Output::Terminal { style, delay } => {
+                let mut drawing = "\x1b[H\x1b[J".as_bytes().to_vec();
+                frame.write(&mut drawing, *style, 1)?;
+                let mut stdout = stdout().lock();
+                stdout.write_all(&drawing)?;
+                stdout.flush()?;
+                sleep(*delay);
+            }
+            Output::Files { directory, style, scale } => {
+                create_dir_all(directory)
+                    .map_err(|err| format!("cannot create directory {}: {err}", directory.display()))?;
+                let path = directory.join(format!("frame-{:05}.{}", self.written, style.extension()));
+                let mut drawing = Vec::new();
+                frame.write(&mut drawing, *style, *scale)?;
+                write(&path, drawing).map_err(|err| format!("cannot write {}: {err}", path.display()))?;
+            }
+            Output::Gif { scale, delay, .. } => {
+                let (width, height, bytes) = frame.rgb(*scale);
+                let (width, height) = (u16::try_from(width)?, u16::try_from(height)?);
+                let encoder = match &mut self.gif {
+                    Some((_, size)) if *size != (width, height) => {
+                        return Err("the frames of a GIF must all have the same size".into());
+                    }
+                    Some((encoder, _)) => encoder,
+                    None => {
+                        let mut encoder = gif::Encoder::new(Vec::new(), width, height, &[])?;
+                        encoder.set_repeat(gif::Repeat::Infinite)?;
+                        &mut self.gif.insert((encoder, (width, height))).0
+                    }
+                };
+                let mut gif_frame = gif_frame(&frame, width, height, &bytes)?;
+                gif_frame.delay = *delay;
+                encoder.write_frame(&gif_frame)?;
+            }
+        }
+        self.written += 1;
+        Ok(())
+    }
+}
+
+/// Encodes the frame with a palette of its own colours, which is exact as
+/// long as there are at most 256 of them, and quantizes it otherwise.
+fn gif_frame(frame: &Frame, width: u16, height: u16, bytes: &[u8]) -> Result<gif::Frame<'static>, Box<dyn Error>> {
+    let mut colors = frame.pixels.iter().map(|(_, pixel)| pixel.color).collect::<Vec<_>>();
+    colors.sort_by_key(|Rgb(red, green, blue)| (*red, *green, *blue));
+    colors.dedup();
+    if colors.len() > 256 {
+        return Ok(gif::Frame::from_rgb_speed(width, height, bytes, 10));
+    }
+    let palette = colors.iter().flat_map(|Rgb(red, green, blue)| [*red, *green, *blue]).collect::<Vec<_>>();
+    let indices = bytes
+        .chunks(3)
+        .map(|color| {
+            let index = colors.binary_search_by_key(&(color[0], color[1], color[2]), |Rgb(red, green, blue)| {
+                (*red, *green, *blue)
+            });
+            index.map(|index| index as u8)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| "a colour is missing from the palette")?;
+    Ok(gif::Frame::from_palette_pixels(width, height, &indices, &palette, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::{read, remove_dir_all, remove_file},
+        process,
+    };
+
+    use super::{FrameSink, Output, Recorder};
+    use crate::{
+        grid::Grid,
+        visualize::{Frame, Pixel, Rgb, Style},
+    };
+
+    fn counter(value: u8) -> Frame {
+        let pixels = Grid::new((0, 0), 2, 1, ()).map(|(x, _), _| match x < value as isize {
+            true => Pixel::new('#', Rgb::GREEN),
+            false => Pixel::new('.', Rgb::BLACK),
+        });
+        Frame::new(format!("count {value}"), pixels)
+    }
+
+    #[test]
+    fn every_nth_frame_and_the_final_one_are_drawn() {
+        let mut kept = Vec::new();
+        let mut keep = |frame: Frame| {
+            kept.push(frame.caption);
+            Ok(())
+        };
+        let mut sink = FrameSink::new(2, &mut keep);
+        for value in 0..=3 {
+            sink.push(|| counter(value)).unwrap();
+        }
+        sink.finish(|| counter(3)).unwrap();
+        assert_eq!(kept, ["count 0", "count 2", "count 3"]);
+    }
+
+    #[test]
+    fn frames_are_written_to_numbered_files() {
+        let directory = temp_dir().join(format!("aoc-animate-{}", process::id()));
+        let output = Output::Files {
+            directory: directory.clone(),
+            style: Style::Ascii,
+            scale: 1,
+        };
+        let mut recorder = Recorder::new(output);
+        for value in 0..=2 {
+            recorder.record(counter(value)).unwrap();
+        }
+        assert_eq!(recorder.finish().unwrap(), 3);
+        let frame = |index| String::from_utf8(read(directory.join(format!("frame-{index:05}.txt"))).unwrap()).unwrap();
+        assert_eq!([frame(0), frame(2)], ["count 0\n..\n", "count 2\n##\n"]);
+        remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn gif_round_trip() {
+        let path = temp_dir().join(format!("aoc-animate-{}.gif", process::id()));
+        let mut recorder = Recorder::new(Output::Gif { path: path.clone(), scale: 2, delay: 5 });
+        for value in 0..=2 {
+            recorder.record(counter(value)).unwrap();
+        }
+        recorder.finish().unwrap();
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let gif = read(&path).unwrap();
+        let mut decoder = options.read_info(&gif[..]).unwrap();
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height, frame.delay), (4, 2, 5));
+            frames.push(frame.buffer[..4].to_vec());
+        }
+        assert_eq!(frames, [[0, 0, 0, 255], [80, 200, 80, 255], [80, 200, 80, 255]]);
+        remove_file(path).unwrap();
+    }
+}
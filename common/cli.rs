@@ -0,0 +1,127 @@
+use std::{
+    error::Error,
+    fs::{File, read_to_string},
+    io::{BufRead, BufReader, Cursor, read_to_string as read_all, stdin},
+    path::{Path, PathBuf},
+};
+
+use clap::{Args, Parser};
+
+use crate::{
+    config::ConfigArgs,
+    explain::Explain,
+    output::{Format, print_answers, print_explanation},
+    parse::{Mode, ParseContext},
+};
+
+/// Selects where a puzzle input is read from.
+#[derive(Args, Clone, Debug, Default)]
+pub struct InputArgs {
+    /// Path to the puzzle input, or `-` to read it from stdin [default: the day's input.txt]
+    #[arg(conflicts_with = "text")]
+    pub input: Option<PathBuf>,
+    /// The puzzle input itself, given inline instead of as a file
+    #[arg(long, value_name = "INPUT")]
+    pub text: Option<String>,
+    /// Skip malformed input lines instead of failing on the first one
+    #[arg(long)]
+    pub lenient: bool,
+}
+
+impl InputArgs {
+    pub fn is_default(&self) -> bool {
+        self.input.is_none() && self.text.is_none()
+    }
+
+    pub fn mode(&self) -> Mode {
+        if self.lenient { Mode::Lenient } else { Mode::Strict }
+    }
+
+    /// Reads the selected input, falling back to `default_path` if none was given.
+    pub fn read(&self, default_path: &str) -> Result<String, Box<dyn Error>> {
+        let text = match (&self.input, &self.text) {
+            (_, Some(text)) => text.clone(),
+            (Some(path), _) if path.as_os_str() == "-" => read_all(stdin())?,
+            (Some(path), _) => read_file(path)?,
+            (None, None) => read_file(Path::new(default_path))?,
+        };
+        Ok(normalize(&text))
+    }
+
+    /// Opens the selected input to be read line by line instead of as a
+    /// whole, leaving line endings and trailing newlines to the reader.
+    pub fn open(&self, default_path: &str) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
+        Ok(match (&self.input, &self.text) {
+            (_, Some(text)) => Box::new(Cursor::new(text.clone().into_bytes())),
+            (Some(path), _) if path.as_os_str() == "-" => Box::new(stdin().lock()),
+            (Some(path), _) => Box::new(BufReader::new(open_file(path)?)),
+            (None, None) => Box::new(BufReader::new(open_file(Path::new(default_path))?)),
+        })
+    }
+}
+
+fn open_file(path: &Path) -> Result<File, Box<dyn Error>> {
+    File::open(path).map_err(|err| format!("cannot read input file {}: {err}", path.display()).into())
+}
+
+fn read_file(path: &Path) -> Result<String, Box<dyn Error>> {
+    read_to_string(path)
+        .map_err(|err| format!("cannot read input file {}: {err}", path.display()).into())
+}
+
+/// Unifies line endings and drops trailing newlines, which the parsers
+/// would otherwise see as an empty last line.
+fn normalize(text: &str) -> String {
+    text.replace("\r\n", "\n").trim_end_matches('\n').to_owned()
+}
+
+/// Lists the input lines that were skipped in lenient mode on stderr.
+pub fn print_skipped(context: &ParseContext) {
+    for error in context.skipped() {
+        eprintln!("skipped {error}");
+    }
+}
+
+/// The options of every day's binary.
+#[derive(Args)]
+pub struct DayArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+    #[command(flatten)]
+    pub config: ConfigArgs,
+    /// How to print the answers
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+    /// Print the intermediate values the answers are built from before them
+    #[arg(long)]
+    pub explain: bool,
+}
+
+/// Solves a single day of Advent of Code 2022.
+#[derive(Parser)]
+struct DayCli {
+    #[command(flatten)]
+    day: DayArgs,
+}
+
+/// The entry point of every day's binary.
+pub fn main<P: Explain>() -> Result<(), Box<dyn Error>> {
+    run::<P>(DayCli::parse().day)
+}
+
+/// Solves a day with the given options, for binaries that add their own.
+pub fn run<P: Explain>(args: DayArgs) -> Result<(), Box<dyn Error>> {
+    let config = args.config.load::<P::Config>(P::DAY)?;
+    let input = args.input.read(P::INPUT_PATH)?;
+    let mut context = ParseContext::new(P::DAY, args.input.mode());
+    let result = P::solve_timed(&input, &mut context, &config);
+    print_skipped(&context);
+    let (answers, timings) = result?;
+    if args.explain {
+        // Parsed a second time, as solving keeps its parsed input to itself. Skipped lines were listed above.
+        let input = P::parse(&input, &mut ParseContext::new(P::DAY, args.input.mode()))?;
+        print_explanation(P::DAY, &P::explain(&input, &config)?, args.format);
+    }
+    print_answers(P::DAY, &answers.to_strings(), &timings, args.format);
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::{error::Error, fs::read_to_string, path::PathBuf};
+
+use clap::Args;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use toml::{Value, value::Table};
+
+/// The configuration of days that have no constants worth tuning.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NoConfig {}
+
+/// Selects the settings a day runs with, on top of the day's defaults.
+#[derive(Args, Clone, Debug, Default)]
+pub struct ConfigArgs {
+    /// A TOML file with a table of settings per day, e.g. `[15]` followed by `row = 2000000`
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    /// Overrides a single setting, taking precedence over the config file
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    pub overrides: Vec<String>,
+}
+
+impl ConfigArgs {
+    /// Builds the configuration of a day from its defaults, the day's table
+    /// in the config file and the overrides, in that order.
+    pub fn load<C>(&self, day: &str) -> Result<C, Box<dyn Error>>
+    where
+        C: Default + Serialize + DeserializeOwned,
+    {
+        let mut settings = match Value::try_from(C::default())? {
+            Value::Table(settings) => settings,
+            _ => return Err(format!("the configuration of day {day} is not a table").into()),
+        };
+        if let Some(path) = &self.config {
+            let text = read_to_string(path)
+                .map_err(|err| format!("cannot read config file {}: {err}", path.display()))?;
+            let file = toml::from_str::<Table>(&text)
+                .map_err(|err| format!("cannot parse config file {}: {err}", path.display()))?;
+            if let Some(day_settings) = file.get(day) {
+                let day_settings = day_settings
+                    .as_table()
+                    .ok_or_else(|| format!("[{day}] in config file {} is not a table", path.display()))?;
+                settings.extend(day_settings.clone());
+            }
+        }
+        for setting in &self.overrides {
+            let (key, value) = setting
+                .split_once('=')
+                .ok_or_else(|| format!("expected KEY=VALUE, got {setting:?}"))?;
+            settings.insert(key.trim().to_owned(), parse_value(value.trim()));
+        }
+        Value::Table(settings)
+            .try_into()
+            .map_err(|err| format!("invalid configuration for day {day}: {err}").into())
+    }
+}
+
+/// Reads a value as TOML, so that `--set rounds=5` gives a number, falling
+/// back to a plain string for bare words.
+fn parse_value(text: &str) -> Value {
+    format!("value = {text}")
+        .parse::<Value>()
+        .ok()
+        .and_then(|document| document.get("value").cloned())
+        .unwrap_or_else(|| Value::String(text.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::{ConfigArgs, NoConfig};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Example {
+        rounds: usize,
+        source: (isize, isize),
+    }
+
+    impl Default for Example {
+        fn default() -> Self {
+            Self { rounds: 20, source: (500, 0) }
+        }
+    }
+
+    fn with_overrides(overrides: &[&str]) -> ConfigArgs {
+        ConfigArgs {
+            config: None,
+            overrides: overrides.iter().map(|setting| setting.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn defaults_without_settings() {
+        assert_eq!(ConfigArgs::default().load::<Example>("11").unwrap(), Example::default());
+        assert!(ConfigArgs::default().load::<NoConfig>("01").is_ok());
+    }
+
+    #[test]
+    fn overrides_replace_defaults() {
+        let config = with_overrides(&["rounds=5", "source = [1, -2]"]).load::<Example>("11").unwrap();
+        assert_eq!(config, Example { rounds: 5, source: (1, -2) });
+    }
+
+    #[test]
+    fn unknown_or_mistyped_settings_fail() {
+        assert!(with_overrides(&["round=5"]).load::<Example>("11").is_err());
+        assert!(with_overrides(&["rounds=many"]).load::<Example>("11").is_err());
+        assert!(with_overrides(&["rounds"]).load::<Example>("11").is_err());
+    }
+}
@@ -0,0 +1,19 @@
+use std::error::Error;
+
+use crate::{
+    Puzzle,
+    parse::{Mode, ParseContext},
+};
+
+/// Lists the intermediate values a day's answers are built from, to find
+/// out where a wrong answer goes astray.
+pub trait Explain: Puzzle {
+    /// One line per value, in the order the solution computes them.
+    fn explain(input: &Self::Input, config: &Self::Config) -> Result<Vec<String>, Box<dyn Error>>;
+
+    /// Parses the input, failing on the first malformed line, and explains its answers.
+    fn explain_str(input: &str, config: &Self::Config) -> Result<Vec<String>, Box<dyn Error>> {
+        let input = Self::parse(input, &mut ParseContext::new(Self::DAY, Mode::Strict))?;
+        Self::explain(&input, config)
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    error::Error,
+    io::{BufRead, Write},
+    str::FromStr,
+};
+
+use crate::Puzzle;
+
+/// Answers questions about a day's parsed input, one command at a time.
+pub trait Explore: Puzzle {
+    /// The commands `query` understands, each as its usage and what it shows.
+    const COMMANDS: &'static [(&'static str, &'static str)];
+
+    /// Runs a command with its arguments, returning the lines to print.
+    fn query(input: &Self::Input, config: &Self::Config, command: &str, arguments: &[&str])
+    -> Result<Vec<String>, Box<dyn Error>>;
+
+    /// Reads commands until `quit` or the end of `reader`, printing what
+    /// each one shows or why it failed. `help` lists the commands.
+    fn repl(
+        input: &Self::Input,
+        config: &Self::Config,
+        mut reader: impl BufRead,
+        mut writer: impl Write,
+    ) -> Result<(), Box<dyn Error>> {
+        let builtins = [("help", "lists the commands"), ("quit", "ends the session")];
+        loop {
+            write!(writer, "{}> ", Self::DAY)?;
+            writer.flush()?;
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 {
+                writeln!(writer)?;
+                return Ok(());
+            }
+            let lines = match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [] => continue,
+                ["quit" | "exit"] => return Ok(()),
+                ["help"] => {
+                    let commands = Self::COMMANDS.iter().chain(&builtins);
+                    let width = commands.clone().map(|(usage, _)| usage.len()).max().unwrap_or_default();
+                    commands.map(|(usage, about)| format!("{usage:<width$}  {about}")).collect()
+                }
+                [command, ref arguments @ ..] => Self::query(input, config, command, arguments)
+                    .unwrap_or_else(|err| vec![format!("error: {err}")]),
+            };
+            for line in lines {
+                writeln!(writer, "{line}")?;
+            }
+        }
+    }
+}
+
+/// The error for a command that a day does not know.
+pub fn unknown_command(command: &str) -> Box<dyn Error> {
+    format!("unknown command {command:?}, try help").into()
+}
+
+/// Parses the only argument of a command, described as `what` in errors.
+pub fn argument<T: FromStr>(arguments: &[&str], what: &str) -> Result<T, Box<dyn Error>> {
+    match arguments {
+        [argument] => Ok(argument.parse().map_err(|_| format!("expected {what}, got {argument:?}"))?),
+        _ => Err(format!("expected {what}").into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::{Explore, argument, unknown_command};
+    use crate::{Puzzle, config::NoConfig, parse::ParseContext};
+
+    struct Numbers;
+
+    impl Puzzle for Numbers {
+        const DAY: &'static str = "00";
+        const INPUT_PATH: &'static str = "";
+
+        type Input = Vec<u32>;
+        type Output1 = u32;
+        type Output2 = u32;
+        type Config = NoConfig;
+
+        fn parse(input: &str, _context: &mut ParseContext) -> Result<Vec<u32>, Box<dyn Error>> {
+            Ok(input.split(',').map(str::parse).collect::<Result<_, _>>()?)
+        }
+
+        fn part1(numbers: &Vec<u32>, _config: &NoConfig) -> Result<u32, Box<dyn Error>> {
+            Ok(numbers.iter().sum())
+        }
+
+        fn part2(numbers: &Vec<u32>, _config: &NoConfig) -> Result<u32, Box<dyn Error>> {
+            Ok(numbers.iter().product())
+        }
+    }
+
+    impl Explore for Numbers {
+        const COMMANDS: &'static [(&'static str, &'static str)] = &[("get INDEX", "the number at INDEX")];
+
+        fn query(numbers: &Vec<u32>, _config: &NoConfig, command: &str, arguments: &[&str]) -> Result<Vec<String>, Box<dyn Error>> {
+            match command {
+                "get" => {
+                    let index = argument::<usize>(arguments, "an index")?;
+                    Ok(vec![numbers.get(index).ok_or("no such number")?.to_string()])
+                }
+                _ => Err(unknown_command(command)),
+            }
+        }
+    }
+
+    #[test]
+    fn commands_run_until_quit() {
+        let mut output = Vec::new();
+        let commands = "get 1\n\nget x\nfrob\nhelp\nquit\nget 0\n";
+        Numbers::repl(&vec![4, 5], &NoConfig::default(), commands.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            output.split("00> ").collect::<Vec<_>>(),
+            [
+                "",
+                "5\n",
+                "",
+                "error: expected an index, got \"x\"\n",
+                "error: unknown command \"frob\", try help\n",
+                "get INDEX  the number at INDEX\nhelp       lists the commands\nquit       ends the session\n",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn session_ends_with_the_input() {
+        let mut output = Vec::new();
+        Numbers::repl(&vec![4], &NoConfig::default(), "get 3".as_bytes(), &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "00> error: no such number\n00> \n");
+    }
+}
@@ -0,0 +1,21 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::Puzzle;
+
+/// The random number generator behind the input generators, chosen because
+/// it gives the same numbers for the same seed on every platform and version.
+pub type InputRng = ChaCha8Rng;
+
+pub fn input_rng(seed: u64) -> InputRng {
+    InputRng::seed_from_u64(seed)
+}
+
+/// Produces random inputs that follow the rules of the puzzle, so that the
+/// solvers can be run on inputs larger and stranger than the real one.
+pub trait Generate: Puzzle {
+    /// Writes an input whose number of elements (lines, blocks, grid rows,
+    /// ... depending on the day) grows with `size`, valid for the given
+    /// configuration.
+    fn generate(rng: &mut InputRng, size: usize, config: &Self::Config) -> String;
+}
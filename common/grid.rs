@@ -0,0 +1,352 @@
+use std::{
+    collections::HashSet,
+    ops::{Index, IndexMut},
+};
+
+use crate::parse::Expected;
+
+/// A position on a grid as `(x, y)`, with `y` growing downwards.
+pub type Point = (isize, isize);
+
+/// The offsets to the right, left, lower and upper neighbours.
+pub const DIRECTIONS_4: [Point; 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+/// The offsets to all eight surrounding neighbours, diagonals included.
+pub const DIRECTIONS_8: [Point; 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+pub fn add((x, y): Point, (dx, dy): Point) -> Point {
+    (x + dx, y + dy)
+}
+
+/// The most cells a grid sized by coordinates from an input may have, so
+/// that far-flung coordinates fail instead of exhausting memory.
+pub const MAX_CELLS: usize = 1 << 24;
+
+/// A dense rectangular grid whose top left cell sits at `origin`, so that
+/// grids can cover coordinates that do not start at zero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    origin: Point,
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(origin: Point, width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            origin,
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid covering the smallest rectangle around both corners.
+    pub fn spanning((x1, y1): Point, (x2, y2): Point, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let origin = (x1.min(x2), y1.min(y2));
+        let width = x1.abs_diff(x2) + 1;
+        let height = y1.abs_diff(y2) + 1;
+        Self::new(origin, width, height, fill)
+    }
+
+    /// Like `spanning`, but `None` if the grid would have more than `MAX_CELLS` cells.
+    pub fn try_spanning((x1, y1): Point, (x2, y2): Point, fill: T) -> Option<Self>
+    where
+        T: Clone,
+    {
+        let width = x1.abs_diff(x2).checked_add(1)?;
+        let height = y1.abs_diff(y2).checked_add(1)?;
+        (width.checked_mul(height)? <= MAX_CELLS).then(|| Self::spanning((x1, y1), (x2, y2), fill))
+    }
+
+    /// Parses one cell per character, requiring every line to be equally long.
+    pub fn parse<F>(text: &str, mut parse_cell: F) -> Result<Self, Expected>
+    where
+        F: FnMut(Point, char) -> Result<T, Expected>,
+    {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in text.split('\n').enumerate() {
+            let mut line_width = 0;
+            for (x, char) in line.chars().enumerate() {
+                let cell = parse_cell((x as isize, y as isize), char).map_err(|expected| Expected {
+                    line: y,
+                    column: x,
+                    ..expected
+                })?;
+                cells.push(cell);
+                line_width += 1;
+            }
+            let width = *width.get_or_insert(line_width);
+            if line_width != width {
+                return Err(Expected {
+                    line: y,
+                    column: line_width.min(width),
+                    expected: format!("a row of {width} cells"),
+                });
+            }
+            height += 1;
+        }
+        Ok(Self {
+            origin: (0, 0),
+            width: width.unwrap_or_default(),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    fn index_of(&self, (x, y): Point) -> Option<usize> {
+        let column = usize::try_from(x - self.origin.0).ok().filter(|column| *column < self.width)?;
+        let row = usize::try_from(y - self.origin.1).ok().filter(|row| *row < self.height)?;
+        Some(row * self.width + column)
+    }
+
+    fn point_of(&self, index: usize) -> Point {
+        (
+            self.origin.0 + (index % self.width) as isize,
+            self.origin.1 + (index / self.width) as isize,
+        )
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|index| &mut self.cells[index])
+    }
+
+    /// All cells in reading order, with their positions.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().enumerate().map(|(index, cell)| (self.point_of(index), cell))
+    }
+
+    /// A grid of the same shape and origin with every cell converted by `convert`.
+    pub fn map<U>(&self, mut convert: impl FnMut(Point, &T) -> U) -> Grid<U> {
+        Grid {
+            origin: self.origin,
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(point, cell)| convert(point, cell)).collect(),
+        }
+    }
+
+    /// The neighbours of a point in the given directions that lie inside the grid.
+    pub fn neighbors<'g>(&'g self, point: Point, directions: &'g [Point]) -> impl Iterator<Item = (Point, &'g T)> {
+        directions.iter().filter_map(move |direction| {
+            let neighbor = add(point, *direction);
+            Some((neighbor, self.get(neighbor)?))
+        })
+    }
+
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors(point, &DIRECTIONS_4)
+    }
+
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors(point, &DIRECTIONS_8)
+    }
+
+    /// The cells met when walking from `point` in `direction` until leaving
+    /// the grid, not including the starting cell.
+    pub fn ray(&self, point: Point, direction: Point) -> impl Iterator<Item = (Point, &T)> {
+        let mut current = point;
+        std::iter::from_fn(move || {
+            current = add(current, direction);
+            Some((current, self.get(current)?))
+        })
+    }
+
+    /// Draws one character per cell, with rows separated by newlines.
+    pub fn render(&self, render_cell: impl Fn(&T) -> char) -> String {
+        self.cells
+            .chunks(self.width.max(1))
+            .map(|row| row.iter().map(&render_cell).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point).expect("point outside of the grid")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point).expect("point outside of the grid")
+    }
+}
+
+/// A set of points, kept as a dense grid of flags when the rectangle the
+/// points fall in has at most `MAX_CELLS` cells, and as a hash set when it
+/// is larger, so that far-flung points cost memory by their number alone.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PointSet {
+    Dense(Grid<bool>),
+    Sparse(HashSet<Point>),
+}
+
+impl PointSet {
+    /// An empty set for points within the rectangle spanned by both corners.
+    pub fn spanning(corner1: Point, corner2: Point) -> Self {
+        Grid::try_spanning(corner1, corner2, false).map_or_else(|| Self::Sparse(HashSet::new()), Self::Dense)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        match self {
+            Self::Dense(grid) => grid.get(point).copied().unwrap_or(false),
+            Self::Sparse(points) => points.contains(&point),
+        }
+    }
+
+    /// Adds a point, which must lie within the rectangle the set spans.
+    pub fn insert(&mut self, point: Point) {
+        match self {
+            Self::Dense(grid) => grid[point] = true,
+            Self::Sparse(points) => {
+                points.insert(point);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Dense(grid) => grid.iter().filter(|(_, cell)| **cell).count(),
+            Self::Sparse(points) => points.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The points in the set, in no particular order.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = Point> + '_> {
+        match self {
+            Self::Dense(grid) => Box::new(grid.iter().filter(|(_, cell)| **cell).map(|(point, _)| point)),
+            Self::Sparse(points) => Box::new(points.iter().copied()),
+        }
+    }
+
+    /// The grid of flags, unless the set is too spread out to keep one.
+    pub fn grid(&self) -> Option<&Grid<bool>> {
+        match self {
+            Self::Dense(grid) => Some(grid),
+            Self::Sparse(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Expected, Grid, Point, PointSet};
+
+    fn digits(text: &str) -> Grid<u32> {
+        Grid::parse(text, |_, char| char.to_digit(10).ok_or_else(|| Expected::line("a digit"))).unwrap()
+    }
+
+    #[test]
+    fn parse_and_render_round_trip() {
+        let grid = digits("123\n456");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.render(|digit| char::from_digit(*digit, 10).unwrap()), "123\n456");
+    }
+
+    #[test]
+    fn parse_reports_bad_cells_and_ragged_rows() {
+        let expected = Grid::parse("12\n3x", |_, char| char.to_digit(10).ok_or_else(|| Expected::line("a digit")))
+            .unwrap_err();
+        assert_eq!((expected.line, expected.column), (1, 1));
+        let expected = Grid::parse("12\n3", |_, char| Ok(char)).unwrap_err();
+        assert_eq!((expected.line, expected.column), (1, 1));
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let grid = digits("123\n456\n789");
+        let corner = grid.neighbors4((0, 0)).map(|(_, digit)| *digit).collect::<Vec<_>>();
+        assert_eq!(corner, [2, 4]);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn ray_walks_to_the_edge() {
+        let grid = digits("123\n456\n789");
+        let ray = grid.ray((0, 0), (1, 1)).map(|(point, _)| point).collect::<Vec<Point>>();
+        assert_eq!(ray, [(1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn try_spanning_refuses_huge_grids() {
+        assert!(Grid::try_spanning((0, 0), (4095, 4095), false).is_some());
+        assert!(Grid::try_spanning((0, 0), (4096, 4096), false).is_none());
+        assert!(Grid::try_spanning((isize::MIN, 0), (isize::MAX, 0), false).is_none());
+    }
+
+    #[test]
+    fn origin_offsets_points() {
+        let mut grid = Grid::spanning((498, 4), (503, 9), false);
+        assert_eq!((grid.width(), grid.height()), (6, 6));
+        grid[(500, 9)] = true;
+        assert!(grid.contains((498, 4)) && !grid.contains((497, 4)) && !grid.contains((504, 9)));
+        assert_eq!(grid.iter().filter(|(_, cell)| **cell).map(|(point, _)| point).collect::<Vec<_>>(), [(500, 9)]);
+        let mapped = grid.map(|(x, _), cell| *cell || x == 498);
+        assert_eq!(mapped.origin(), (498, 4));
+        assert_eq!(mapped.iter().filter(|(_, cell)| **cell).count(), 7);
+    }
+
+    #[test]
+    fn point_sets_fall_back_to_sparse() {
+        for (corner, dense) in [((9, 9), true), ((8192, 8192), false)] {
+            let mut points = PointSet::spanning((0, 0), corner);
+            assert_eq!(points.grid().is_some(), dense);
+            points.insert((3, 4));
+            points.insert((3, 4));
+            points.insert(corner);
+            assert!(points.contains((3, 4)) && !points.contains((4, 3)) && !points.contains((-1, 0)));
+            assert_eq!(points.len(), 2);
+            let mut listed = points.iter().collect::<Vec<_>>();
+            listed.sort();
+            assert_eq!(listed, [(3, 4), corner]);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::parse::{Mode, ParseContext};
+
+pub mod animate;
+pub mod cli;
+pub mod config;
+pub mod explain;
+pub mod explore;
+pub mod generate;
+pub mod grid;
+pub mod ocr;
+pub mod output;
+pub mod parse;
+pub mod visualize;
+
+/// The answers to both parts of a day's puzzle.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answers<P1, P2> {
+    pub part1: P1,
+    pub part2: P2,
+}
+
+impl<P1: Display, P2: Display> Answers<P1, P2> {
+    pub fn to_strings(&self) -> Answers<String, String> {
+        Answers {
+            part1: self.part1.to_string(),
+            part2: self.part2.to_string(),
+        }
+    }
+
+    /// Prints both answers as `2022-12-XX Part N: ...` lines, moving
+    /// multi-line answers (like the day 10 CRT) below their heading.
+    pub fn print(&self, day: &str) {
+        for (part, answer) in [self.part1.to_string(), self.part2.to_string()].into_iter().enumerate() {
+            if answer.contains('\n') {
+                println!("2022-12-{day} Part {}:\n{answer}", part + 1);
+            } else {
+                println!("2022-12-{day} Part {}: {answer}", part + 1);
+            }
+        }
+    }
+}
+
+/// How long each phase of solving a puzzle took.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+/// A puzzle's answers together with how long it took to compute them.
+pub type TimedAnswers<P1, P2> = (Answers<P1, P2>, Timings);
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// The shape shared by every day: parse the input once, then compute
+/// both parts from the parsed representation.
+pub trait Puzzle {
+    /// The two-digit day number, e.g. `"07"`.
+    const DAY: &'static str;
+    /// The location of the day's bundled `input.txt`.
+    const INPUT_PATH: &'static str;
+
+    type Input;
+    type Output1: Display;
+    type Output2: Display;
+    /// The puzzle's constants, whose defaults give the answers to the real puzzle.
+    type Config: Default + Serialize + DeserializeOwned;
+
+    fn parse(input: &str, context: &mut ParseContext) -> Result<Self::Input, Box<dyn Error>>;
+
+    fn part1(input: &Self::Input, config: &Self::Config) -> Result<Self::Output1, Box<dyn Error>>;
+
+    fn part2(input: &Self::Input, config: &Self::Config) -> Result<Self::Output2, Box<dyn Error>>;
+
+    /// Solves the puzzle with the default configuration, failing on the first malformed line.
+    fn solve(input: &str) -> Result<Answers<Self::Output1, Self::Output2>, Box<dyn Error>> {
+        Self::solve_with(input, &Self::Config::default())
+    }
+
+    /// Solves the puzzle with the given configuration, failing on the first malformed line.
+    fn solve_with(input: &str, config: &Self::Config) -> Result<Answers<Self::Output1, Self::Output2>, Box<dyn Error>> {
+        let mut context = ParseContext::new(Self::DAY, Mode::Strict);
+        Self::solve_timed(input, &mut context, config).map(|(answers, _)| answers)
+    }
+
+    /// Solves the puzzle while measuring parsing and both parts separately.
+    fn solve_timed(
+        input: &str,
+        context: &mut ParseContext,
+        config: &Self::Config,
+    ) -> Result<TimedAnswers<Self::Output1, Self::Output2>, Box<dyn Error>> {
+        let (input, parse_time) = timed(|| Self::parse(input, context));
+        let input = input?;
+        let (part1, part1_time) = timed(|| Self::part1(&input, config));
+        let part1 = part1?;
+        let (part2, part2_time) = timed(|| Self::part2(&input, config));
+        let part2 = part2?;
+        let timings = Timings {
+            parse: parse_time,
+            part1: part1_time,
+            part2: part2_time,
+        };
+        Ok((Answers { part1, part2 }, timings))
+    }
+}
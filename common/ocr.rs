@@ -0,0 +1,71 @@
+/// The glyphs of the 4x6 capital letter font drawn by the day 10 CRT, row
+/// by row, with `#` for a lit pixel.
+const FONT: [(char, &str); 16] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+
+/// Reads the letters drawn in six rows of pixel art, where every letter is
+/// four pixels wide and followed by one blank column. Any character other
+/// than a space or `.` counts as a lit pixel.
+pub fn decode(art: &str) -> Option<String> {
+    let rows = art
+        .split('\n')
+        .map(|row| row.chars().map(|pixel| pixel != ' ' && pixel != '.').collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let width = rows.first()?.len();
+    if rows.len() != GLYPH_HEIGHT || width < GLYPH_WIDTH || rows.iter().any(|row| row.len() != width) {
+        return None;
+    }
+    (0..=width - GLYPH_WIDTH)
+        .step_by(GLYPH_WIDTH + 1)
+        .map(|left| {
+            let glyph = rows
+                .iter()
+                .flat_map(|row| &row[left..left + GLYPH_WIDTH])
+                .map(|lit| if *lit { '#' } else { '.' })
+                .collect::<String>();
+            FONT.iter().find(|(_, pixels)| *pixels == glyph).map(|(letter, _)| *letter)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode;
+
+    #[test]
+    fn decodes_letters() {
+        let art = "\
+█  █ ████ \n\
+█  █    █ \n\
+████   █  \n\
+█  █  █   \n\
+█  █ █    \n\
+█  █ ████ ";
+        assert_eq!(decode(art).as_deref(), Some("HZ"));
+    }
+
+    #[test]
+    fn rejects_unknown_shapes() {
+        assert_eq!(decode("████\n████\n████\n████\n████\n████"), None);
+        assert_eq!(decode("single line"), None);
+    }
+}
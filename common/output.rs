@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::{Answers, Timings, ocr};
+
+/// How answers are written to stdout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// `2022-12-XX Part N: ...` lines meant for people
+    #[default]
+    Text,
+    /// One JSON object per part and line, meant for other programs
+    Json,
+}
+
+/// The answer to one part of a day, as written by `--format json`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnswerRecord {
+    pub day: String,
+    pub part: u8,
+    pub answer: String,
+    /// The letters drawn by multi-line answers such as the day 10 CRT.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// How long computing the part took, in nanoseconds.
+    pub duration: u64,
+}
+
+impl AnswerRecord {
+    fn new(day: &str, part: u8, answer: String, duration: Duration) -> Self {
+        let text = if answer.contains('\n') { ocr::decode(&answer) } else { None };
+        Self {
+            day: day.to_owned(),
+            part,
+            answer,
+            text,
+            duration: duration.as_nanos() as u64,
+        }
+    }
+}
+
+/// The explanation of a day's answers, as written by `--format json`.
+#[derive(Debug, Serialize)]
+pub struct ExplanationRecord<'e> {
+    pub day: &'e str,
+    pub explanation: &'e [String],
+}
+
+/// Prints the explanation of a day's answers in the given format.
+pub fn print_explanation(day: &str, explanation: &[String], format: Format) {
+    match format {
+        Format::Text => {
+            println!("2022-12-{day} Explanation:");
+            for line in explanation {
+                println!("  {line}");
+            }
+        }
+        Format::Json => {
+            let record = ExplanationRecord { day, explanation };
+            println!("{}", serde_json::to_string(&record).unwrap_or_default());
+        }
+    }
+}
+
+/// Prints the answers of a day in the given format.
+pub fn print_answers(day: &str, answers: &Answers<String, String>, timings: &Timings, format: Format) {
+    match format {
+        Format::Text => answers.print(day),
+        Format::Json => {
+            let records = [
+                AnswerRecord::new(day, 1, answers.part1.clone(), timings.part1),
+                AnswerRecord::new(day, 2, answers.part2.clone(), timings.part2),
+            ];
+            for record in records {
+                println!("{}", serde_json::to_string(&record).unwrap_or_default());
+            }
+        }
+    }
+}
@@ -0,0 +1,369 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// How malformed lines in an input are treated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Fail on the first malformed line.
+    #[default]
+    Strict,
+    /// Skip malformed lines, collecting them so they can be listed afterwards.
+    Lenient,
+}
+
+/// What a parser expected to find, at a position relative to the start of
+/// the text it was given.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expected {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+/// A malformed part of a day's input, with 1-based line and column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: &'static str,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+impl Expected {
+    /// An error about a line as a whole, pointing at its first column.
+    pub fn line(expected: impl Into<String>) -> Self {
+        Self {
+            line: 0,
+            column: 0,
+            expected: expected.into(),
+        }
+    }
+}
+
+/// Carries the parsing mode through a day's parser and collects the lines
+/// skipped in lenient mode.
+pub struct ParseContext {
+    day: &'static str,
+    mode: Mode,
+    skipped: Vec<ParseError>,
+}
+
+impl ParseContext {
+    pub fn new(day: &'static str, mode: Mode) -> Self {
+        Self {
+            day,
+            mode,
+            skipped: Vec::new(),
+        }
+    }
+
+    pub fn skipped(&self) -> &[ParseError] {
+        &self.skipped
+    }
+
+    /// Turns a position relative to text starting on the 0-based line
+    /// `first_line` of the input into an error for the whole input.
+    pub fn error(&self, first_line: usize, expected: Expected) -> ParseError {
+        ParseError {
+            day: self.day,
+            line: first_line + expected.line + 1,
+            column: expected.column + 1,
+            expected: expected.expected,
+        }
+    }
+
+    /// Fails in strict mode, and records the error and skips the item in lenient mode.
+    pub fn check<T>(&mut self, first_line: usize, result: Result<T, Expected>) -> Result<Option<T>, ParseError> {
+        match (result, self.mode) {
+            (Ok(item), _) => Ok(Some(item)),
+            (Err(expected), Mode::Strict) => Err(self.error(first_line, expected)),
+            (Err(expected), Mode::Lenient) => {
+                let error = self.error(first_line, expected);
+                self.skipped.push(error);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Parses each line of `text`, which starts on the 0-based line `first_line` of the input.
+    pub fn lines<'t, T, F>(&mut self, text: &'t str, first_line: usize, mut parse: F) -> Result<Vec<T>, ParseError>
+    where
+        F: FnMut(&'t str) -> Result<T, Expected>,
+    {
+        let mut items = Vec::new();
+        for (index, line) in text.split('\n').enumerate() {
+            items.extend(self.check(first_line + index, parse(line))?);
+        }
+        Ok(items)
+    }
+
+    /// Parses each blank-line separated block of `text`, which starts on the
+    /// 0-based line `first_line` of the input.
+    pub fn blocks<'t, T, F>(&mut self, text: &'t str, first_line: usize, mut parse: F) -> Result<Vec<T>, ParseError>
+    where
+        F: FnMut(&'t str) -> Result<T, Expected>,
+    {
+        let mut items = Vec::new();
+        let mut line = first_line;
+        for block in text.split("\n\n") {
+            items.extend(self.check(line, parse(block))?);
+            line += block.split('\n').count() + 1;
+        }
+        Ok(items)
+    }
+}
+
+/// Walks through a piece of text token by token, keeping track of where it
+/// is so that failures can name a line and column.
+#[derive(Clone)]
+pub struct Cursor<'t> {
+    text: &'t str,
+    position: usize,
+}
+
+impl<'t> Cursor<'t> {
+    pub fn new(text: &'t str) -> Self {
+        Self { text, position: 0 }
+    }
+
+    pub fn rest(&self) -> &'t str {
+        &self.text[self.position..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    /// An error at the current position.
+    pub fn expected(&self, expected: impl Into<String>) -> Expected {
+        let consumed = &self.text[..self.position];
+        let line_start = consumed.rfind('\n').map_or(0, |index| index + 1);
+        Expected {
+            line: consumed.matches('\n').count(),
+            column: consumed[line_start..].chars().count(),
+            expected: expected.into(),
+        }
+    }
+
+    pub fn tag(&mut self, tag: &str) -> Result<(), Expected> {
+        if self.eat(tag) { Ok(()) } else { Err(self.expected(format!("{tag:?}"))) }
+    }
+
+    /// Consumes `tag` if it comes next, telling whether it did. Unlike a
+    /// failed `tag`, looking ahead this way costs nothing when it fails.
+    pub fn eat(&mut self, tag: &str) -> bool {
+        let found = self.rest().starts_with(tag);
+        if found {
+            self.position += tag.len();
+        }
+        found
+    }
+
+    /// Consumes whichever of the options comes next.
+    pub fn one_of<'o>(&mut self, options: &[&'o str]) -> Result<&'o str, Expected> {
+        let option = options
+            .iter()
+            .find(|option| self.rest().starts_with(**option))
+            .ok_or_else(|| self.expected(format!("one of {options:?}")))?;
+        self.position += option.len();
+        Ok(option)
+    }
+
+    /// Consumes characters as long as they satisfy the predicate.
+    pub fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'t str {
+        let rest = self.rest();
+        let length = rest.find(|char| !predicate(char)).unwrap_or(rest.len());
+        self.position += length;
+        &rest[..length]
+    }
+
+    /// Consumes a single character that satisfies the predicate.
+    pub fn char(&mut self, expected: &str, predicate: impl Fn(char) -> bool) -> Result<char, Expected> {
+        match self.rest().chars().next().filter(|char| predicate(*char)) {
+            Some(char) => {
+                self.position += char.len_utf8();
+                Ok(char)
+            }
+            None => Err(self.expected(expected)),
+        }
+    }
+
+    /// Consumes a decimal number without a sign.
+    pub fn number<T: FromStr>(&mut self) -> Result<T, Expected> {
+        let digits = self.rest().find(|char: char| !char.is_ascii_digit()).unwrap_or(self.rest().len());
+        self.parse_number(digits)
+    }
+
+    /// Consumes an optionally negative decimal number.
+    pub fn signed<T: FromStr>(&mut self) -> Result<T, Expected> {
+        let rest = self.rest();
+        let sign = if rest.starts_with('-') { 1 } else { 0 };
+        let digits = rest[sign..].find(|char: char| !char.is_ascii_digit()).unwrap_or(rest.len() - sign);
+        match digits {
+            0 => Err(self.expected("a number")),
+            _ => self.parse_number(sign + digits),
+        }
+    }
+
+    /// Parses the next `length` bytes as a number, telling numbers that are
+    /// missing apart from those too large for `T`.
+    fn parse_number<T: FromStr>(&mut self, length: usize) -> Result<T, Expected> {
+        let number = match length {
+            0 => return Err(self.expected("a number")),
+            _ => self.rest()[..length].parse().map_err(|_| self.expected("a smaller number"))?,
+        };
+        self.position += length;
+        Ok(number)
+    }
+
+    /// Consumes one or more items with `separator` between each two.
+    pub fn separated<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T, Expected>,
+    ) -> Result<Vec<T>, Expected> {
+        let mut items = vec![item(self)?];
+        while self.eat(separator) {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Consumes the indentation and key of a `key: value` line, leaving the
+    /// value to be parsed.
+    pub fn field(&mut self, key: &str) -> Result<(), Expected> {
+        self.take_while(|char| char == ' ');
+        self.tag(key)?;
+        self.tag(": ")
+    }
+
+    /// Consumes the rest of the current line, which must not be empty.
+    pub fn line(&mut self, expected: &str) -> Result<&'t str, Expected> {
+        match self.take_while(|char| char != '\n') {
+            "" => Err(self.expected(expected)),
+            line => Ok(line),
+        }
+    }
+
+    /// Succeeds only if all of the text has been consumed.
+    pub fn end(&self) -> Result<(), Expected> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.expected("the end of the line"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cursor, Expected, Mode, ParseContext};
+
+    fn range(text: &str) -> Result<(u8, u8), Expected> {
+        let mut cursor = Cursor::new(text);
+        let start = cursor.number()?;
+        cursor.tag("-")?;
+        let end = cursor.number()?;
+        cursor.end()?;
+        Ok((start, end))
+    }
+
+    #[test]
+    fn cursor_reports_column() {
+        let expected = range("12x4").unwrap_err();
+        assert_eq!((expected.line, expected.column), (0, 2));
+        assert_eq!(expected.expected, "\"-\"");
+        assert_eq!(range("1-300").unwrap_err().column, 2);
+        assert_eq!(range("-7-1").unwrap_err().column, 0);
+    }
+
+    #[test]
+    fn numbers_and_signs() {
+        let mut cursor = Cursor::new("-12,300");
+        assert_eq!(cursor.number::<i32>().unwrap_err().expected, "a number");
+        assert_eq!(cursor.signed::<i32>(), Ok(-12));
+        cursor.tag(",").unwrap();
+        let expected = cursor.number::<u8>().unwrap_err();
+        assert_eq!((expected.column, expected.expected.as_str()), (4, "a smaller number"));
+        assert_eq!(Cursor::new("-x").signed::<i32>().unwrap_err().expected, "a number");
+        let mut cursor = Cursor::new("é1");
+        assert_eq!(cursor.char("a letter", char::is_alphabetic), Ok('é'));
+        assert_eq!(cursor.char("a letter", char::is_alphabetic).unwrap_err().column, 1);
+    }
+
+    #[test]
+    fn separated_fields() {
+        let mut cursor = Cursor::new("  Starting items: 79, 98\nnext");
+        cursor.field("Starting items").unwrap();
+        assert_eq!(cursor.separated(", ", |cursor| cursor.number::<u8>()), Ok(vec![79, 98]));
+        cursor.tag("\n").unwrap();
+        assert_eq!(cursor.line("a name"), Ok("next"));
+        assert_eq!(cursor.line("a name").unwrap_err().column, 4);
+        let expected = Cursor::new("  Test 3").field("Test").unwrap_err();
+        assert_eq!((expected.column, expected.expected.as_str()), (6, "\": \""));
+    }
+
+    #[test]
+    fn eat_looks_ahead() {
+        let mut cursor = Cursor::new("old * 3");
+        assert!(!cursor.eat("new"));
+        assert!(cursor.eat("old"));
+        assert!(!cursor.eat("old"));
+        assert_eq!(cursor.rest(), " * 3");
+    }
+
+    #[test]
+    fn cursor_reports_line_within_block() {
+        let mut cursor = Cursor::new("a\nbc");
+        cursor.tag("a\nb").unwrap();
+        let expected = cursor.tag("x").unwrap_err();
+        assert_eq!((expected.line, expected.column), (1, 1));
+    }
+
+    #[test]
+    fn strict_fails_on_first_bad_line() {
+        let mut context = ParseContext::new("04", Mode::Strict);
+        let error = context.lines("1-2\n3-4\n5-\n7", 0, range).unwrap_err();
+        assert_eq!(error.to_string(), "day 04, line 3, column 3: expected a number");
+    }
+
+    #[test]
+    fn lenient_skips_bad_lines() {
+        let mut context = ParseContext::new("04", Mode::Lenient);
+        let ranges = context.lines("1-2\nx\n3-4\n5-", 0, range).unwrap();
+        assert_eq!(ranges, [(1, 2), (3, 4)]);
+        let lines = context.skipped().iter().map(|error| error.line).collect::<Vec<_>>();
+        assert_eq!(lines, [2, 4]);
+    }
+
+    #[test]
+    fn blocks_keep_line_numbers() {
+        let mut context = ParseContext::new("01", Mode::Lenient);
+        let blocks = context.blocks("1\n2\n\n3\nx\n\n5", 0, |block| {
+            let mut cursor = Cursor::new(block);
+            let mut numbers = vec![cursor.number::<u8>()?];
+            while !cursor.is_empty() {
+                cursor.tag("\n")?;
+                numbers.push(cursor.number()?);
+            }
+            Ok(numbers)
+        });
+        assert_eq!(blocks.unwrap(), [vec![1, 2], vec![5]]);
+        assert_eq!(context.skipped()[0].line, 5);
+    }
+}
@@ -0,0 +1,240 @@
+use std::{error::Error, io::Write, path::Path};
+
+use clap::ValueEnum;
+
+use crate::{
+    Puzzle,
+    grid::Grid,
+    parse::{Mode, ParseContext},
+};
+
+/// A colour given by its red, green and blue components.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Self = Self(0, 0, 0);
+    pub const DARK_GREY: Self = Self(60, 60, 60);
+    pub const GREY: Self = Self(140, 140, 140);
+    pub const WHITE: Self = Self(255, 255, 255);
+    pub const RED: Self = Self(220, 50, 47);
+    pub const GREEN: Self = Self(80, 200, 80);
+    pub const BLUE: Self = Self(60, 110, 220);
+    pub const YELLOW: Self = Self(240, 200, 60);
+
+    /// The colour `amount` of the way from `self` to `other`, with `amount`
+    /// clamped to between 0 and 1.
+    pub fn mix(self, other: Self, amount: f64) -> Self {
+        let amount = amount.clamp(0.0, 1.0);
+        let channel = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * amount).round() as u8;
+        Self(channel(self.0, other.0), channel(self.1, other.1), channel(self.2, other.2))
+    }
+}
+
+/// One cell of a frame: the character shown in a terminal, and the colour
+/// it is shown in there and filled with in images.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pixel {
+    pub glyph: char,
+    pub color: Rgb,
+}
+
+impl Pixel {
+    pub const fn new(glyph: char, color: Rgb) -> Self {
+        Self { glyph, color }
+    }
+}
+
+/// A picture of a puzzle's state with one pixel per grid cell, together
+/// with a line describing it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub caption: String,
+    pub pixels: Grid<Pixel>,
+}
+
+impl Frame {
+    pub fn new(caption: impl Into<String>, pixels: Grid<Pixel>) -> Self {
+        Self {
+            caption: caption.into(),
+            pixels,
+        }
+    }
+
+    /// The caption followed by the glyphs, without any colour.
+    pub fn ascii(&self) -> String {
+        format!("{}\n{}", self.caption, self.pixels.render(|pixel| pixel.glyph))
+    }
+
+    /// The caption followed by the glyphs in their colours, as 24-bit ANSI
+    /// escape codes that most terminals understand.
+    pub fn ansi(&self) -> String {
+        let mut text = format!("{}\n", self.caption);
+        let mut color = None;
+        for (index, (_, pixel)) in self.pixels.iter().enumerate() {
+            if index > 0 && index % self.pixels.width() == 0 {
+                text.push_str("\x1b[0m\n");
+                color = None;
+            }
+            if color != Some(pixel.color) {
+                let Rgb(red, green, blue) = pixel.color;
+                text.push_str(&format!("\x1b[38;2;{red};{green};{blue}m"));
+                color = Some(pixel.color);
+            }
+            text.push(pixel.glyph);
+        }
+        text + "\x1b[0m"
+    }
+
+    /// The colours as rows of RGB bytes, with every pixel grown into a
+    /// square `scale` pixels wide. Returns the width, height and bytes.
+    pub(crate) fn rgb(&self, scale: usize) -> (usize, usize, Vec<u8>) {
+        let (width, height) = (self.pixels.width() * scale, self.pixels.height() * scale);
+        let mut bytes = Vec::with_capacity(width * height * 3);
+        let rows = self.pixels.iter().map(|(_, pixel)| pixel.color).collect::<Vec<_>>();
+        for row in rows.chunks(self.pixels.width().max(1)) {
+            let line = row
+                .iter()
+                .flat_map(|Rgb(red, green, blue)| [*red, *green, *blue].repeat(scale))
+                .collect::<Vec<_>>();
+            for _ in 0..scale {
+                bytes.extend_from_slice(&line);
+            }
+        }
+        (width, height, bytes)
+    }
+
+    /// Writes the frame as a binary PPM image.
+    pub fn write_ppm(&self, mut writer: impl Write, scale: usize) -> Result<(), Box<dyn Error>> {
+        let (width, height, bytes) = self.rgb(scale);
+        write!(writer, "P6\n{width} {height}\n255\n")?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// Writes the frame as a PNG image.
+    pub fn write_png(&self, writer: impl Write, scale: usize) -> Result<(), Box<dyn Error>> {
+        let (width, height, bytes) = self.rgb(scale);
+        if width == 0 || height == 0 {
+            return Err("cannot write an empty picture as a PNG image".into());
+        }
+        let mut encoder = png::Encoder::new(writer, u32::try_from(width)?, u32::try_from(height)?);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&bytes)?;
+        Ok(())
+    }
+
+    /// Writes the frame in the given style, scaling images by `scale`.
+    pub fn write(&self, mut writer: impl Write, style: Style, scale: usize) -> Result<(), Box<dyn Error>> {
+        match style {
+            Style::Ascii => writeln!(writer, "{}", self.ascii())?,
+            Style::Ansi => writeln!(writer, "{}", self.ansi())?,
+            Style::Ppm => self.write_ppm(writer, scale)?,
+            Style::Png => self.write_png(writer, scale)?,
+        }
+        Ok(())
+    }
+}
+
+/// How a frame is drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Style {
+    /// Plain characters
+    Ascii,
+    /// Characters coloured for a terminal
+    Ansi,
+    /// A PPM image
+    Ppm,
+    /// A PNG image
+    Png,
+}
+
+impl Style {
+    /// The extension of files drawn in this style.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Ascii => "txt",
+            Self::Ansi => "ans",
+            Self::Ppm => "ppm",
+            Self::Png => "png",
+        }
+    }
+
+    /// The image style matching the extension of a file name, if any.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "ppm" => Some(Self::Ppm),
+            "png" => Some(Self::Png),
+            "txt" => Some(Self::Ascii),
+            _ => None,
+        }
+    }
+}
+
+/// Draws the state a day's solution works through, to see what it does.
+pub trait Visualize: Puzzle {
+    fn visualize(input: &Self::Input, config: &Self::Config) -> Result<Frame, Box<dyn Error>>;
+
+    /// Parses the input, failing on the first malformed line, and draws it.
+    fn draw(input: &str, config: &Self::Config) -> Result<Frame, Box<dyn Error>> {
+        let input = Self::parse(input, &mut ParseContext::new(Self::DAY, Mode::Strict))?;
+        Self::visualize(&input, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{Frame, Pixel, Rgb, Style};
+    use crate::grid::Grid;
+
+    fn checkers() -> Frame {
+        let mut pixels = Grid::new((0, 0), 2, 2, Pixel::new('.', Rgb::BLACK));
+        pixels[(1, 0)] = Pixel::new('#', Rgb::RED);
+        pixels[(0, 1)] = Pixel::new('#', Rgb::RED);
+        Frame::new("checkers", pixels)
+    }
+
+    #[test]
+    fn text_styles() {
+        assert_eq!(checkers().ascii(), "checkers\n.#\n#.");
+        assert_eq!(
+            checkers().ansi(),
+            "checkers\n\x1b[38;2;0;0;0m.\x1b[38;2;220;50;47m#\x1b[0m\n\x1b[38;2;220;50;47m#\x1b[38;2;0;0;0m.\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn ppm_scales_pixels() {
+        let mut ppm = Vec::new();
+        checkers().write_ppm(&mut ppm, 2).unwrap();
+        let (header, bytes) = ppm.split_at(b"P6\n4 4\n255\n".len());
+        assert_eq!(header, b"P6\n4 4\n255\n");
+        assert_eq!(bytes.len(), 4 * 4 * 3);
+        assert_eq!(&bytes[..12], [0, 0, 0, 0, 0, 0, 220, 50, 47, 220, 50, 47]);
+        assert_eq!(bytes[12..24], bytes[..12]);
+    }
+
+    #[test]
+    fn png_round_trip() {
+        let mut png = Vec::new();
+        checkers().write_png(&mut png, 3).unwrap();
+        let mut reader = png::Decoder::new(&png[..]).read_info().unwrap();
+        let mut bytes = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut bytes).unwrap();
+        assert_eq!((info.width, info.height), (6, 6));
+        let mut ppm = Vec::new();
+        checkers().write_ppm(&mut ppm, 3).unwrap();
+        assert!(ppm.ends_with(&bytes[..info.buffer_size()]));
+    }
+
+    #[test]
+    fn mix_and_style_from_path() {
+        assert_eq!(Rgb::BLACK.mix(Rgb::WHITE, 0.5), Rgb(128, 128, 128));
+        assert_eq!(Rgb::BLACK.mix(Rgb::WHITE, 2.0), Rgb::WHITE);
+        assert_eq!(Style::from_path(Path::new("cave.PNG")), Some(Style::Png));
+        assert_eq!(Style::from_path(Path::new("cave")), None);
+    }
+}
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// The search of part 2 takes seconds with the real bound, too slow for fuzzing.
+const CONFIG: day15::Config = day15::Config { row: 10, search_bound: 100 };
+
+fuzz_target!(|data: &[u8]| aoc_fuzz::check::<day15::Day15>(data, &CONFIG));
@@ -0,0 +1,21 @@
+//! Fuzz targets for every day, one per binary, run with e.g.
+//! `cargo +nightly fuzz run day07` from this directory.
+
+use common::{
+    Puzzle,
+    parse::{Mode, ParseContext},
+};
+
+/// Parses arbitrary bytes in both modes and solves whatever parses. Errors
+/// are the expected outcome for most inputs; any panic is a bug.
+pub fn check<P: Puzzle>(data: &[u8], config: &P::Config) {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    for mode in [Mode::Strict, Mode::Lenient] {
+        if let Ok(parsed) = P::parse(input, &mut ParseContext::new(P::DAY, mode)) {
+            let _ = P::part1(&parsed, config);
+            let _ = P::part2(&parsed, config);
+        }
+    }
+}